@@ -3,7 +3,8 @@
 //! it — there is no board. Assignment IS the bead's assignee; done IS the
 //! bead closing. Murmur reads the ready frontier to brief and route, and
 //! writes exactly three transitions: assign (in_progress + assignee),
-//! done (closed with attribution), drop (open again).
+//! done (closed with attribution), drop (open again) — plus a comment
+//! when a reassignment carries a handoff note.
 //!
 //! We shell out to the `bd` CLI the same way we shell out to `herdr` — no
 //! SDK, no socket, and murmur never touches `.beads/` internals. Mutating
@@ -11,6 +12,7 @@
 //! success (some builds print a checkmark), and closing an already-closed
 //! bead succeeds — the goal state holds.
 
+use crate::exit::{self, Kind};
 use anyhow::{bail, Context, Result};
 use serde_json::{json, Value};
 use std::path::{Path, PathBuf};
@@ -22,6 +24,14 @@ pub struct Issue {
     pub body: String,
     pub parent: Option<String>,
     pub status: String,
+    pub assignee: String,
+    /// Why it closed — `murmur done` writes the closer into it.
+    pub close_reason: String,
+    /// Last change, unix secs (0 when bd didn't say).
+    pub updated: u64,
+    /// Beads this one waits on (`blocks` dependencies) — restack merges
+    /// their branches first.
+    pub blockers: Vec<String>,
 }
 
 impl Issue {
@@ -67,7 +77,25 @@ fn beads_root(cwd: &Path) -> Option<PathBuf> {
 /// Fetch one bead by id (`bd-a1b2`).
 pub fn fetch(id: &str) -> Result<Issue> {
     let v = call(&["show", id, "--json"])?;
-    first_issue(&v).with_context(|| format!("no bead '{id}'"))
+    first_issue(&v).ok_or_else(|| exit::err(Kind::NotFound, format!("no bead '{id}'")))
+}
+
+/// The goal's children: `bd show` lists them as parent-child dependents.
+pub fn children(id: &str) -> Result<Vec<String>> {
+    let v = call(&["show", id, "--json"])?;
+    let node = v.as_array().and_then(|a| a.first()).cloned().unwrap_or(v);
+    Ok(dependent_ids(&node))
+}
+
+/// Who closed a bead, read back from the reason `murmur done` wrote
+/// ("… — closed by w1 via murmur", "Completed by w1 via murmur.").
+pub fn closer(reason: &str) -> Option<String> {
+    let rest = reason
+        .rfind("closed by ")
+        .map(|i| &reason[i + "closed by ".len()..])
+        .or_else(|| reason.strip_prefix("Completed by "))?;
+    let name = rest.split(" via murmur").next()?.trim();
+    (!name.is_empty() && !name.contains(' ')).then(|| name.to_string())
 }
 
 /// Create a bead from a goal string, so the work has a durable home.
@@ -91,6 +119,27 @@ pub fn ready_in(cwd: Option<&Path>) -> Result<Vec<Issue>> {
     Ok(issue_list(&v))
 }
 
+/// Beads someone is working right now — whoever is assignee here is busy.
+pub fn in_progress() -> Result<Vec<Issue>> {
+    in_progress_in(None)
+}
+
+pub fn in_progress_in(cwd: Option<&Path>) -> Result<Vec<Issue>> {
+    let v = call_in(cwd, &["list", "--status", "in_progress", "--json"])?;
+    Ok(issue_list(&v))
+}
+
+/// Is `id` part of `goal`'s tree: a listed child, a parent link, or a
+/// hierarchical id (`bd-a1b2.3.1` under `bd-a1b2`).
+pub fn under(issue: &Issue, goal: &str, children: &[String]) -> bool {
+    issue.parent.as_deref() == Some(goal)
+        || children.iter().any(|c| c == &issue.id)
+        || issue
+            .id
+            .strip_prefix(goal)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
 /// Ready beads that are not the parent of another ready bead. Work is
 /// assigned at leaves; the epic stays in beads until the children close.
 pub fn leaves(issues: &[Issue]) -> Vec<&Issue> {
@@ -115,6 +164,11 @@ pub fn assign(id: &str, agent: &str) -> Result<()> {
     ])
 }
 
+/// Leave a note on the bead — a reassignment's handoff, for the record.
+pub fn comment(id: &str, text: &str) -> Result<()> {
+    call_mut(&["comments", "add", id, text, "--json"])
+}
+
 /// Close a bead with attribution, treating "already closed" as success:
 /// if beads got there first, the goal state holds — stop.
 pub fn close(id: &str, reason: &str) -> Result<()> {
@@ -152,9 +206,15 @@ fn call_in(cwd: Option<&Path>, args: &[&str]) -> Result<Value> {
     if let Some(cwd) = cwd {
         cmd.current_dir(cwd);
     }
-    let out = cmd
-        .output()
-        .with_context(|| format!("failed to run '{}' — is beads installed?", bin().display()))?;
+    let out = cmd.output().map_err(|e| {
+        exit::err(
+            Kind::Missing,
+            format!(
+                "failed to run '{}' — is beads installed? ({e})",
+                bin().display()
+            ),
+        )
+    })?;
     if !out.status.success() {
         let err = String::from_utf8_lossy(&out.stderr);
         let out_s = String::from_utf8_lossy(&out.stdout);
@@ -208,15 +268,114 @@ fn parse_issue(v: &Value) -> Option<Issue> {
         .and_then(|x| x.as_str())
         .unwrap_or_default();
     let status = v.get("status").and_then(|x| x.as_str()).unwrap_or_default();
+    let assignee = v
+        .get("assignee")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default();
+    let close_reason = v
+        .get("close_reason")
+        .or_else(|| v.get("closed_reason"))
+        .and_then(|x| x.as_str())
+        .unwrap_or_default();
+    let updated = v
+        .get("updated_at")
+        .or_else(|| v.get("updated"))
+        .and_then(|x| x.as_str())
+        .and_then(parse_time)
+        .unwrap_or(0);
     Some(Issue {
         id: id.to_string(),
         title: title.to_string(),
         body: body.to_string(),
         parent: parse_parent(v),
         status: status.to_string(),
+        assignee: assignee.to_string(),
+        close_reason: close_reason.to_string(),
+        updated,
+        blockers: blocker_ids(v),
     })
 }
 
+/// RFC 3339 (`2026-10-18T08:32:01.5-07:00`, `...Z`) to unix seconds, no
+/// date crate: days from the civil calendar (Howard Hinnant's algorithm),
+/// the inverse of `commands::fmt_utc`.
+pub fn parse_time(s: &str) -> Option<u64> {
+    let num = |a: usize, b: usize| s.get(a..b)?.parse::<i64>().ok();
+    let (y, m, d) = (num(0, 4)?, num(5, 7)?, num(8, 10)?);
+    let (hh, mm, ss) = (num(11, 13)?, num(14, 16)?, num(17, 19)?);
+    let tail = s.get(19..)?;
+    let zone = tail.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match zone {
+        "" | "Z" | "z" => 0,
+        z => {
+            let sign = match z.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let h = z.get(1..3)?.parse::<i64>().ok()?;
+            let min = z.get(4..6)?.parse::<i64>().ok()?;
+            sign * (h * 3600 + min * 60)
+        }
+    };
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    let secs = days * 86_400 + hh * 3600 + mm * 60 + ss - offset;
+    u64::try_from(secs).ok()
+}
+
+/// Ids of a bead's parent-child dependents (an untyped entry counts: some
+/// builds only list children there).
+fn dependent_ids(v: &Value) -> Vec<String> {
+    v.get("dependents")
+        .and_then(|d| d.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|d| {
+            d.get("dependency_type")
+                .or_else(|| d.get("type"))
+                .and_then(|t| t.as_str())
+                .is_none_or(|t| t == "parent-child")
+        })
+        .filter_map(|d| {
+            d.get("id")
+                .or_else(|| d.get("issue_id"))
+                .and_then(|x| x.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        })
+        .collect()
+}
+
+/// Ids this bead depends on with a `blocks` edge. `bd show` lists them as
+/// full issues carrying `dependency_type`, `bd list` as edges with
+/// `depends_on_id`; parent-child and soft links don't order anything.
+fn blocker_ids(v: &Value) -> Vec<String> {
+    v.get("dependencies")
+        .and_then(|d| d.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|d| {
+            d.get("dependency_type")
+                .or_else(|| d.get("type"))
+                .and_then(|t| t.as_str())
+                == Some("blocks")
+        })
+        .filter_map(|d| {
+            d.get("depends_on_id")
+                .or_else(|| d.get("id"))
+                .and_then(|x| x.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        })
+        .collect()
+}
+
 fn parse_parent(v: &Value) -> Option<String> {
     if let Some(s) = v
         .get("parent")
@@ -251,7 +410,7 @@ fn parse_parent(v: &Value) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{issue_list, leaves};
+    use super::{blocker_ids, closer, dependent_ids, issue_list, leaves, under};
     use serde_json::json;
 
     #[test]
@@ -284,6 +443,21 @@ mod tests {
         assert_eq!(issue_list(&dep)[0].parent.as_deref(), Some("bd-1"));
     }
 
+    #[test]
+    fn blockers_parse_from_show_and_list_shapes() {
+        let show = json!({"id": "bd-1.3", "dependencies": [
+            {"id": "bd-1", "dependency_type": "parent-child"},
+            {"id": "bd-1.1", "dependency_type": "blocks"},
+            {"id": "bd-7", "dependency_type": "related"}
+        ]});
+        assert_eq!(blocker_ids(&show), vec!["bd-1.1"]);
+        let list = json!({"id": "bd-1.3", "dependencies": [
+            {"issue_id": "bd-1.3", "depends_on_id": "bd-1.2", "type": "blocks"}
+        ]});
+        assert_eq!(blocker_ids(&list), vec!["bd-1.2"]);
+        assert!(blocker_ids(&json!({"id": "bd-9"})).is_empty());
+    }
+
     #[test]
     fn leaves_drop_parents_of_ready_children() {
         let issues = issue_list(&json!([
@@ -295,6 +469,63 @@ mod tests {
         assert_eq!(ids, vec!["bd-1.2", "bd-9"]);
     }
 
+    #[test]
+    fn closer_reads_back_the_done_attribution() {
+        assert_eq!(
+            closer("shelves render — closed by w1 via murmur").as_deref(),
+            Some("w1")
+        );
+        assert_eq!(
+            closer("Completed by codex-2 via murmur.").as_deref(),
+            Some("codex-2")
+        );
+        assert_eq!(closer("fixed upstream"), None);
+    }
+
+    #[test]
+    fn children_are_parent_child_dependents() {
+        let v = json!({"id": "bd-1", "dependents": [
+            {"id": "bd-1.1", "dependency_type": "parent-child"},
+            {"id": "bd-7", "dependency_type": "blocks"},
+            {"id": "bd-1.2"}
+        ]});
+        assert_eq!(dependent_ids(&v), vec!["bd-1.1", "bd-1.2"]);
+    }
+
+    #[test]
+    fn under_follows_parent_children_and_dotted_ids() {
+        let issues = issue_list(&json!([
+            {"id": "bd-1.2", "title": "dotted"},
+            {"id": "bd-7", "title": "linked", "parent": "bd-1"},
+            {"id": "bd-8", "title": "listed"},
+            {"id": "bd-10", "title": "not a child of bd-1"}
+        ]));
+        let children = vec!["bd-8".to_string()];
+        let ids: Vec<&str> = issues
+            .iter()
+            .filter(|i| under(i, "bd-1", &children))
+            .map(|i| i.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["bd-1.2", "bd-7", "bd-8"]);
+    }
+
+    #[test]
+    fn updated_times_parse_with_zones() {
+        let v = json!([
+            {"id": "bd-1", "updated_at": "2026-10-18T08:32:01Z"},
+            {"id": "bd-2", "updated_at": "2026-10-18T01:32:01.123456-07:00"},
+            {"id": "bd-3", "updated_at": "yesterday"}
+        ]);
+        let list = issue_list(&v);
+        assert_eq!(list[0].updated, 1_792_312_321);
+        assert_eq!(list[1].updated, list[0].updated);
+        assert_eq!(list[2].updated, 0);
+        assert_eq!(
+            crate::commands::fmt_utc(list[0].updated),
+            "2026-10-18 08:32:01Z"
+        );
+    }
+
     #[test]
     fn closed_status_parses() {
         let v = json!({"id": "bd-1", "title": "t", "status": "closed"});
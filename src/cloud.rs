@@ -16,6 +16,7 @@
 //! output comes back through the git host as a branch/PR. The lead learns
 //! each launch id by durable mail at start time.
 
+use crate::exit::{self, Kind};
 use anyhow::{bail, Context, Result};
 use serde_json::{json, Value};
 use std::io::Write;
@@ -259,9 +260,12 @@ fn curl(method: &str, url: &str, body: Option<&Value>) -> Result<Value> {
     let key = std::env::var(CURSOR_KEY_ENV)
         .ok()
         .filter(|s| !s.is_empty())
-        .context(
-            "cloud:cursor needs CURSOR_API_KEY (Cursor dashboard → API keys, or ~/.secrets)",
-        )?;
+        .ok_or_else(|| {
+            exit::err(
+                Kind::Missing,
+                "cloud:cursor needs CURSOR_API_KEY (Cursor dashboard → API keys, or ~/.secrets)",
+            )
+        })?;
     let mut cmd = Command::new(curl_bin());
     cmd.args([
         "-sS",
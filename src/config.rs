@@ -0,0 +1,507 @@
+//! `murmur.toml` — the flags every wave in this repo would repeat.
+//!
+//! Found the way FLEET.md is: nearest one walking up from cwd. It holds
+//! defaults for `murmur start` (and the planning lead of `murmur plan`),
+//! named presets layered over them (`murmur start --preset frontend`),
+//! restack's gate command, and how long start waits for an agent to settle.
+//! A flag on the command line always wins, then the preset, then the file,
+//! then murmur's own default; `murmur config show` prints what that comes
+//! to here.
+//!
+//! ```toml
+//! [start]
+//! kind = "claude,codex=2"
+//! worktree = true
+//! worktree_cmd = "pnpm worktree:new"
+//! hubs = ["src/registry.ts"]
+//! with = "pnpm dev"
+//! ready_timeout = "2m"
+//!
+//! [restack]
+//! cmd = "pnpm test"
+//!
+//! [preset.frontend]
+//! kind = "claude=3"
+//! hubs = ["src/routes.ts"]
+//! ```
+//!
+//! There is no TOML crate behind this: the file is the subset above —
+//! tables, `key = value` with strings, integers, booleans, and one-line
+//! string arrays, `#` comments. Anything else, or a key murmur doesn't
+//! know, is an error naming the line, so a typo never silently does nothing.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const FILE: &str = "murmur.toml";
+
+/// Agents `murmur start` brings up when nothing says otherwise.
+pub const WORKERS: usize = 2;
+
+/// Without a file, a flag, or MURMUR_READY_TIMEOUT_MS.
+pub const READY_TIMEOUT_MS: u64 = 90_000;
+
+/// Start's flags, as the file (or a preset) sets them; None is unset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StartDefaults {
+    pub kind: Option<String>,
+    pub workers: Option<usize>,
+    pub worktree: Option<bool>,
+    pub worktree_cmd: Option<String>,
+    pub hubs: Option<Vec<String>>,
+    pub with: Option<String>,
+    pub board: Option<String>,
+}
+
+impl StartDefaults {
+    /// `over`'s settings where it has them, ours elsewhere.
+    fn layered(&self, over: &StartDefaults) -> StartDefaults {
+        StartDefaults {
+            kind: over.kind.clone().or_else(|| self.kind.clone()),
+            workers: over.workers.or(self.workers),
+            worktree: over.worktree.or(self.worktree),
+            worktree_cmd: over
+                .worktree_cmd
+                .clone()
+                .or_else(|| self.worktree_cmd.clone()),
+            hubs: over.hubs.clone().or_else(|| self.hubs.clone()),
+            with: over.with.clone().or_else(|| self.with.clone()),
+            board: over.board.clone().or_else(|| self.board.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Config {
+    /// The file these came from; None when there is none.
+    pub path: Option<PathBuf>,
+    pub start: StartDefaults,
+    /// `[start] ready_timeout`, in milliseconds.
+    pub ready_timeout_ms: Option<u64>,
+    /// `[restack] cmd`: the gate a fresh restack runs after each merge.
+    pub restack_cmd: Option<String>,
+    pub presets: BTreeMap<String, StartDefaults>,
+}
+
+impl Config {
+    /// The start defaults with `preset` (if any) layered on.
+    pub fn start_with(&self, preset: Option<&str>) -> Result<StartDefaults> {
+        let Some(name) = preset else {
+            return Ok(self.start.clone());
+        };
+        match self.presets.get(name) {
+            Some(p) => Ok(self.start.layered(p)),
+            None if self.presets.is_empty() => crate::fail!(
+                NotFound,
+                "no preset '{name}' — {} defines none",
+                self.source()
+            ),
+            None => crate::fail!(
+                NotFound,
+                "no preset '{name}' — {} has: {}",
+                self.source(),
+                self.presets.keys().cloned().collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    fn source(&self) -> String {
+        self.path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| format!("there is no {FILE}, so it"))
+    }
+}
+
+/// Nearest murmur.toml walking up from cwd, like FLEET.md and .murmur.
+pub fn find() -> Option<PathBuf> {
+    crate::store::find_up(FILE)
+}
+
+/// The config in effect here: the nearest file's, or all defaults.
+pub fn load() -> Result<Config> {
+    match find() {
+        Some(path) => load_from(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+pub fn load_from(path: &Path) -> Result<Config> {
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let mut cfg = parse(&text).with_context(|| format!("in {}", path.display()))?;
+    cfg.path = Some(path.to_path_buf());
+    Ok(cfg)
+}
+
+/// How long start waits for a new agent to settle: MURMUR_READY_TIMEOUT_MS,
+/// else the file's `ready_timeout`, else 90s.
+pub fn ready_timeout_ms() -> u64 {
+    if let Some(ms) = std::env::var("MURMUR_READY_TIMEOUT_MS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+    {
+        return ms;
+    }
+    load()
+        .ok()
+        .and_then(|c| c.ready_timeout_ms)
+        .unwrap_or(READY_TIMEOUT_MS)
+}
+
+/// `murmur config show` — what start and restack would use here, and
+/// where each value comes from.
+pub fn show(preset: Option<String>, json: bool) -> Result<()> {
+    let cfg = load()?;
+    let d = cfg.start_with(preset.as_deref())?;
+    let from = |in_preset: bool, in_file: bool| -> String {
+        match (&preset, in_preset, in_file) {
+            (Some(p), true, _) => format!("preset {p}"),
+            (_, _, true) => FILE.to_string(),
+            _ => "default".to_string(),
+        }
+    };
+    let p = preset
+        .as_deref()
+        .and_then(|name| cfg.presets.get(name))
+        .cloned()
+        .unwrap_or_default();
+    let f = &cfg.start;
+    let env_timeout = std::env::var("MURMUR_READY_TIMEOUT_MS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok());
+    let ready_ms = ready_timeout_ms();
+    if json {
+        return crate::report::emit(&serde_json::json!({
+            "file": cfg.path,
+            "preset": preset,
+            "start": {
+                "kind": d.kind,
+                "workers": d.workers.unwrap_or(WORKERS),
+                "worktree": d.worktree.unwrap_or(false),
+                "worktree_cmd": d.worktree_cmd,
+                "hubs": d.hubs.clone().unwrap_or_default(),
+                "with": d.with,
+                "board": d.board,
+            },
+            "ready_timeout_ms": ready_ms,
+            "restack": { "cmd": cfg.restack_cmd },
+            "presets": cfg.presets.keys().collect::<Vec<_>>(),
+        }));
+    }
+    let none = || "—".to_string();
+    let rows = [
+        (
+            "start.kind",
+            d.kind.clone().unwrap_or_else(none),
+            from(p.kind.is_some(), f.kind.is_some()),
+        ),
+        (
+            "start.workers",
+            d.workers.unwrap_or(WORKERS).to_string(),
+            from(p.workers.is_some(), f.workers.is_some()),
+        ),
+        (
+            "start.worktree",
+            d.worktree.unwrap_or(false).to_string(),
+            from(p.worktree.is_some(), f.worktree.is_some()),
+        ),
+        (
+            "start.worktree_cmd",
+            d.worktree_cmd.clone().unwrap_or_else(none),
+            from(p.worktree_cmd.is_some(), f.worktree_cmd.is_some()),
+        ),
+        (
+            "start.hubs",
+            d.hubs.clone().unwrap_or_default().join(", "),
+            from(p.hubs.is_some(), f.hubs.is_some()),
+        ),
+        (
+            "start.with",
+            d.with.clone().unwrap_or_else(none),
+            from(p.with.is_some(), f.with.is_some()),
+        ),
+        (
+            "start.board",
+            d.board.clone().unwrap_or_else(none),
+            from(p.board.is_some(), f.board.is_some()),
+        ),
+        (
+            "start.ready_timeout",
+            format!("{}ms", ready_ms),
+            match (env_timeout, cfg.ready_timeout_ms) {
+                (Some(_), _) => "MURMUR_READY_TIMEOUT_MS".to_string(),
+                (None, Some(_)) => FILE.to_string(),
+                (None, None) => "default".to_string(),
+            },
+        ),
+        (
+            "restack.cmd",
+            cfg.restack_cmd.clone().unwrap_or_else(none),
+            from(false, cfg.restack_cmd.is_some()),
+        ),
+    ];
+    println!(
+        "file     {}",
+        cfg.path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| format!("none (no {FILE} here or above)"))
+    );
+    if !cfg.presets.is_empty() {
+        println!(
+            "presets  {}",
+            cfg.presets.keys().cloned().collect::<Vec<_>>().join(", ")
+        );
+    }
+    println!();
+    for (key, value, source) in rows {
+        println!("{key:<20} {value:<32} ({source})");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Str(String),
+    Int(u64),
+    Bool(bool),
+    List(Vec<String>),
+}
+
+fn parse(text: &str) -> Result<Config> {
+    let mut cfg = Config::default();
+    let mut table = String::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = strip_comment(raw).trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+        let at = || format!("line {}", i + 1);
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .with_context(|| format!("{}: unclosed table header", at()))?
+                .trim();
+            match name.split_once('.') {
+                None if matches!(name, "start" | "restack") => {}
+                Some(("preset", p)) if !p.is_empty() && !p.contains('.') => {
+                    cfg.presets.entry(p.to_string()).or_default();
+                }
+                _ => bail!(
+                    "{}: unknown table [{name}] (murmur.toml has [start], [restack], \
+                     [preset.<name>])",
+                    at()
+                ),
+            }
+            table = name.to_string();
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("{}: expected key = value", at()))?;
+        let key = key.trim();
+        let value = parse_value(value.trim()).with_context(at)?;
+        set(&mut cfg, &table, key, value).with_context(at)?;
+    }
+    Ok(cfg)
+}
+
+fn set(cfg: &mut Config, table: &str, key: &str, value: Value) -> Result<()> {
+    match (table, key) {
+        ("start", "ready_timeout") => {
+            let text = string(key, value)?;
+            let secs = crate::store::parse_duration(&text)?;
+            let ms = secs
+                .checked_mul(1000)
+                .with_context(|| format!("ready_timeout '{text}' is too long"))?;
+            cfg.ready_timeout_ms = Some(ms);
+        }
+        ("restack", "cmd") => cfg.restack_cmd = Some(string(key, value)?),
+        ("start", _) => set_start(&mut cfg.start, key, value)?,
+        (t, _) if t.starts_with("preset.") => {
+            let preset = cfg.presets.entry(t["preset.".len()..].to_string());
+            set_start(preset.or_default(), key, value)?;
+        }
+        ("", _) => bail!("'{key}' must sit under a table: [start], [restack], or [preset.<name>]"),
+        _ => bail!("unknown key '{key}' in [{table}]"),
+    }
+    Ok(())
+}
+
+fn set_start(d: &mut StartDefaults, key: &str, value: Value) -> Result<()> {
+    match key {
+        "kind" => d.kind = Some(string(key, value)?),
+        "workers" => match value {
+            Value::Int(n) if n >= 1 => d.workers = Some(n as usize),
+            _ => bail!("workers must be a whole number, at least 1"),
+        },
+        "worktree" => match value {
+            Value::Bool(b) => d.worktree = Some(b),
+            _ => bail!("worktree must be true or false"),
+        },
+        "worktree_cmd" => d.worktree_cmd = Some(string(key, value)?),
+        "hubs" => match value {
+            Value::List(paths) => d.hubs = Some(paths),
+            _ => bail!("hubs must be a list of paths: [\"src/registry.ts\"]"),
+        },
+        "with" => d.with = Some(string(key, value)?),
+        "board" => d.board = Some(string(key, value)?),
+        _ => bail!(
+            "unknown key '{key}' (start takes kind, workers, worktree, worktree_cmd, hubs, \
+             with, board)"
+        ),
+    }
+    Ok(())
+}
+
+fn string(key: &str, value: Value) -> Result<String> {
+    match value {
+        Value::Str(s) => Ok(s),
+        _ => bail!("{key} must be a string"),
+    }
+}
+
+/// A `#` outside a string starts a comment.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+fn parse_value(s: &str) -> Result<Value> {
+    match s {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+    if let Some(inner) = s.strip_prefix('[') {
+        let inner = inner
+            .strip_suffix(']')
+            .context("a list opens and closes on one line: [\"a\", \"b\"]")?;
+        let mut items = Vec::new();
+        let mut rest = inner.trim();
+        while !rest.is_empty() {
+            let (item, tail) = string_prefix(rest)?;
+            items.push(item);
+            rest = tail.trim_start();
+            rest = match rest.strip_prefix(',') {
+                Some(r) => r.trim_start(),
+                None if rest.is_empty() => rest,
+                None => bail!("expected ',' between list items"),
+            };
+        }
+        return Ok(Value::List(items));
+    }
+    if s.starts_with('"') || s.starts_with('\'') {
+        let (text, tail) = string_prefix(s)?;
+        anyhow::ensure!(tail.trim().is_empty(), "unexpected text after the string");
+        return Ok(Value::Str(text));
+    }
+    match s.replace('_', "").parse() {
+        Ok(n) => Ok(Value::Int(n)),
+        Err(_) => bail!("can't read value '{s}' — quote strings: \"{s}\""),
+    }
+}
+
+/// A leading "basic" (with \" \\ \n \t escapes) or 'literal' string, and
+/// what follows it.
+fn string_prefix(s: &str) -> Result<(String, &str)> {
+    let mut chars = s.char_indices();
+    let Some((_, quote)) = chars.next().filter(|(_, c)| matches!(c, '"' | '\'')) else {
+        bail!("expected a quoted string at '{s}'");
+    };
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c == quote => return Ok((out, &s[i + 1..])),
+            '\\' if quote == '"' => match chars.next().map(|(_, e)| e) {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                other => bail!("unsupported escape \\{}", other.unwrap_or(' ')),
+            },
+            c => out.push(c),
+        }
+    }
+    bail!("unterminated string")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_tables_presets_and_values() {
+        let cfg = parse(
+            r#"
+# this repo's waves
+[start]
+kind = "claude,codex=2"   # lead first
+worktree = true
+worktree_cmd = 'pnpm worktree:new'
+hubs = ["src/registry.ts", "src/a#b.ts"]
+ready_timeout = "2m"
+
+[restack]
+cmd = "pnpm test \"--run\""
+
+[preset.frontend]
+kind = "claude=3"
+workers = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.start.kind.as_deref(), Some("claude,codex=2"));
+        assert_eq!(cfg.start.worktree, Some(true));
+        assert_eq!(cfg.start.worktree_cmd.as_deref(), Some("pnpm worktree:new"));
+        assert_eq!(
+            cfg.start.hubs,
+            Some(vec![
+                "src/registry.ts".to_string(),
+                "src/a#b.ts".to_string()
+            ])
+        );
+        assert_eq!(cfg.ready_timeout_ms, Some(120_000));
+        assert_eq!(cfg.restack_cmd.as_deref(), Some(r#"pnpm test "--run""#));
+        let fe = cfg.start_with(Some("frontend")).unwrap();
+        assert_eq!(fe.kind.as_deref(), Some("claude=3"));
+        assert_eq!(fe.workers, Some(3));
+        assert_eq!(fe.worktree, Some(true), "the preset inherits [start]");
+        assert!(cfg.start_with(Some("backend")).is_err());
+    }
+
+    #[test]
+    fn typos_name_their_line() {
+        for (text, want) in [
+            ("[start]\nkinds = \"grok\"\n", "line 2"),
+            ("[strat]\n", "unknown table"),
+            ("[start]\nworkers = two\n", "quote strings"),
+            ("kind = \"grok\"\n", "under a table"),
+            ("[start]\nhubs = [\"a\"\n", "one line"),
+            (
+                "[start]\nready_timeout = \"100000000000000000\"\n",
+                "too long",
+            ),
+        ] {
+            let err = format!("{:#}", parse(text).unwrap_err());
+            assert!(err.contains(want), "{text:?}: {err}");
+        }
+    }
+}
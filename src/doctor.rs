@@ -9,68 +9,139 @@
 //! revoked key or an exhausted quota shows up here, not mid-herd.
 
 use anyhow::Result;
+use serde::Serialize;
 
 use crate::store::on_path;
 use crate::{beads, cloud, fleet, herdr};
 
-pub fn run() -> Result<()> {
+/// One line of the doctor's verdict; `--json` prints them all.
+#[derive(Serialize)]
+pub struct Check {
+    /// What was checked: herdr, beads, fleet, or a kind.
+    pub name: String,
+    /// ok, warn, or miss.
+    pub status: &'static str,
+    pub detail: String,
+}
+
+/// `murmur doctor --json`.
+#[derive(Serialize)]
+pub struct DoctorReport {
+    pub roster: Option<String>,
+    pub checks: Vec<Check>,
+}
+
+fn check(name: &str, status: &'static str, detail: impl Into<String>) -> Check {
+    Check {
+        name: name.to_string(),
+        status,
+        detail: detail.into(),
+    }
+}
+
+pub fn run(json: bool) -> Result<()> {
+    let report = examine();
+    if json {
+        return crate::report::emit(&report);
+    }
+    let mut checks = report.checks.iter();
+    // herdr and beads, then the roster and its kinds
+    for c in checks.by_ref().take(2) {
+        println!("{:<5} {}", c.status, c.detail);
+    }
+    if let Some(path) = &report.roster {
+        println!("\nroster {path}");
+    }
+    for c in checks {
+        println!("{:<5} {}", c.status, c.detail);
+    }
+    Ok(())
+}
+
+fn examine() -> DoctorReport {
+    let mut checks = Vec::new();
     let herdr_up = herdr::available();
-    if herdr_up {
-        println!("ok    herdr server running");
+    checks.push(if herdr_up {
+        check("herdr", "ok", "herdr server running")
     } else {
-        println!("miss  herdr not running — murmur requires it (panes, presence, delivery)");
-    }
-    if beads::available() {
-        println!("ok    beads (bd) on PATH");
+        check(
+            "herdr",
+            "miss",
+            "herdr not running — murmur requires it (panes, presence, delivery)",
+        )
+    });
+    checks.push(if beads::available() {
+        check("beads", "ok", "beads (bd) on PATH")
     } else {
-        println!(
-            "warn  beads (bd) not on PATH — plan/assign/done need it; goal-string waves still run"
-        );
-    }
+        check(
+            "beads",
+            "warn",
+            "beads (bd) not on PATH — plan/assign/done need it; goal-string waves still run",
+        )
+    });
 
     let Some(path) = fleet::find() else {
-        println!("miss  FLEET.md not found — `murmur setup` seeds one");
-        return Ok(());
+        checks.push(check(
+            "fleet",
+            "miss",
+            "FLEET.md not found — `murmur setup` seeds one",
+        ));
+        return DoctorReport {
+            roster: None,
+            checks,
+        };
     };
-    println!("\nroster {}", path.display());
     let kinds = fleet::kinds();
     if kinds.is_empty() {
-        println!("warn  no kinds found in the roster table");
-        return Ok(());
+        checks.push(check("fleet", "warn", "no kinds found in the roster table"));
     }
     for kind in kinds {
-        if cloud::is_cloud(&kind) {
-            check_cloud(&kind);
+        checks.push(if cloud::is_cloud(&kind) {
+            check_cloud(&kind)
         } else {
-            check_local(&kind, herdr_up);
-        }
+            check_local(&kind, herdr_up)
+        });
+    }
+    DoctorReport {
+        roster: Some(path.display().to_string()),
+        checks,
     }
-    Ok(())
 }
 
-fn check_local(kind: &str, herdr_up: bool) {
+fn check_local(kind: &str, herdr_up: bool) -> Check {
     // Best-effort: herdr's kinds name their canonical executable, so a
     // missing binary is a strong hint, not proof — herdr may still know
     // how to launch it. `cursor` (herdr) vs `cursor-agent` (PATH) is the
     // one we have seen in the wild.
     let bin = on_path(kind) || herdr::kind_aliases(kind).iter().any(|a| on_path(a));
     match (bin, herdr_up) {
-        (true, true) => println!("ok    {kind}"),
-        (true, false) => {
-            println!("warn  {kind}: binary present, but herdr is down — no panes to spawn into")
-        }
-        (false, true) => {
-            println!("warn  {kind}: '{kind}' not on PATH (herdr may still know how to launch it)")
-        }
-        (false, false) => println!("miss  {kind}: no '{kind}' on PATH and herdr is down"),
+        (true, true) => check(kind, "ok", kind),
+        (true, false) => check(
+            kind,
+            "warn",
+            format!("{kind}: binary present, but herdr is down — no panes to spawn into"),
+        ),
+        (false, true) => check(
+            kind,
+            "warn",
+            format!("{kind}: '{kind}' not on PATH (herdr may still know how to launch it)"),
+        ),
+        (false, false) => check(
+            kind,
+            "miss",
+            format!("{kind}: no '{kind}' on PATH and herdr is down"),
+        ),
     }
 }
 
-fn check_cloud(kind: &str) {
+fn check_cloud(kind: &str) -> Check {
     let backend = cloud::backend(kind);
     if !cloud::known_backend(backend) {
-        println!("miss  {kind}: unknown cloud backend (supported: cursor)");
-        return;
+        return check(
+            kind,
+            "miss",
+            format!("{kind}: unknown cloud backend (supported: cursor)"),
+        );
     }
     let mut missing = Vec::new();
     if !cloud::cursor_key_available() {
@@ -83,12 +154,15 @@ fn check_cloud(kind: &str) {
         missing.push("no git 'origin' remote to clone from".to_string());
     }
     if !missing.is_empty() {
-        println!("miss  {kind}: {}", missing.join(", "));
-        return;
+        return check(kind, "miss", format!("{kind}: {}", missing.join(", ")));
     }
     // Config looks launchable — ask the provider itself.
     match cloud::probe(backend) {
-        Ok(()) => println!("ok    {kind} (api answered)"),
-        Err(e) => println!("warn  {kind}: configured, but the api probe failed: {e}"),
+        Ok(()) => check(kind, "ok", format!("{kind} (api answered)")),
+        Err(e) => check(
+            kind,
+            "warn",
+            format!("{kind}: configured, but the api probe failed: {e}"),
+        ),
     }
 }
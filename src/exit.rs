@@ -0,0 +1,93 @@
+//! Exit codes — the part of a failure a script can branch on.
+//!
+//! Most failures are prose for a human and exit 1. The few a script has to
+//! tell apart carry a `Kind`: herdr being down is not a closed bead, and a
+//! restack conflict is not a red gate. Raise one with `fail!` (the
+//! `bail!` of kinded errors) or `exit::err`; `code` finds the kind anywhere
+//! in the error chain, so `.context(...)` on top keeps the code. The codes
+//! are documented in the README — never renumber one.
+
+use std::fmt;
+
+/// 2 is clap's, for a bad command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// herdr, beads, the git host's CLI, or a key isn't there.
+    Missing,
+    /// No such wave, agent, bead, branch, or restack.
+    NotFound,
+    /// A merge stopped on conflicting files.
+    Conflict,
+    /// Merged, but the gate command failed on it.
+    Gate,
+    /// Murmur won't: a closed bead, a dirty tree, a restack already under way.
+    Refused,
+    /// Some of the work happened, some didn't.
+    Partial,
+}
+
+impl Kind {
+    pub fn code(self) -> i32 {
+        match self {
+            Kind::Missing => 3,
+            Kind::NotFound => 4,
+            Kind::Conflict => 5,
+            Kind::Gate => 6,
+            Kind::Refused => 7,
+            Kind::Partial => 8,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Failure {
+    pub kind: Kind,
+    message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// A kinded error, for `ok_or_else` and `map_err`.
+pub fn err(kind: Kind, message: impl Into<String>) -> anyhow::Error {
+    Failure {
+        kind,
+        message: message.into(),
+    }
+    .into()
+}
+
+/// The process exit code for `e`: its kind's, else 1.
+pub fn code(e: &anyhow::Error) -> i32 {
+    e.chain()
+        .find_map(|c| c.downcast_ref::<Failure>())
+        .map_or(1, |f| f.kind.code())
+}
+
+/// `bail!` with a kind: `fail!(Refused, "{id} is already closed")`.
+#[macro_export]
+macro_rules! fail {
+    ($kind:ident, $($arg:tt)*) => {
+        return Err($crate::exit::err($crate::exit::Kind::$kind, format!($($arg)*)))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn the_kind_survives_context() {
+        let e = Err::<(), _>(err(Kind::Conflict, "hub.txt"))
+            .context("restack stopped")
+            .unwrap_err();
+        assert_eq!(code(&e), 5);
+        assert_eq!(code(&anyhow::anyhow!("prose")), 1);
+    }
+}
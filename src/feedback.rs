@@ -0,0 +1,261 @@
+//! `murmur pr feedback` — review comments find their way back to the owner.
+//!
+//! A review on a herd PR lands on the git host, where no agent is looking;
+//! nobody hears about it unless the lead polls. Feedback fetches each herd
+//! branch's new review comments and failed check names through the host's
+//! CLI, maps `herd/<slug>/<name>` back to its agent, and tells that agent
+//! one condensed summary. What was forwarded is remembered in plugin state,
+//! the way the idle-wake's ready-bead nudge remembers beads, so a comment
+//! is only ever news once. The same check runs from the idle-wake plugin
+//! for the pane that just settled, at most every few minutes.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{record, tell_or_spool, Delivery};
+use crate::exit::{self, Kind};
+use crate::fail;
+use crate::githost::{Feedback, Host};
+use crate::say;
+use crate::store::{Event, HerdSnap, Store, Verb};
+
+/// The idle-wake asks the host at most this often per agent.
+const RECHECK_SECS: u64 = 300;
+
+/// Comments quoted in one summary; the rest are counted.
+const MAX_QUOTED: usize = 8;
+
+/// `murmur pr feedback` — forward what's new on every herd branch's PR
+/// (or one agent's) to its owner.
+pub fn run(agent: Option<String>, wave: Option<String>, json: bool) -> Result<()> {
+    crate::report::set_json(json);
+    let store = Store::locate()?;
+    let snap = match (&wave, &agent) {
+        (None, Some(name)) => store.herd_of(name)?,
+        _ => store.herd_select(wave.as_deref())?,
+    }
+    .filter(|w| !w.slug.is_empty())
+    .ok_or_else(|| {
+        exit::err(
+            Kind::NotFound,
+            "no worktree herd — pr feedback serves a `murmur start --worktree` herd",
+        )
+    })?;
+    let names: Vec<String> = match agent {
+        Some(name) => {
+            if !snap.agents.contains(&name) {
+                fail!(NotFound, "{name} is not in wave {}", snap.key());
+            }
+            vec![name]
+        }
+        None => snap.agents.clone(),
+    };
+    let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let host = crate::restack::host_here(&cwd, "pr feedback")?;
+    let state_dir = crate::herdr::plugin_state_dir(&store);
+    let mut report = Vec::new();
+    for name in &names {
+        let branch = format!("herd/{}/{name}", snap.slug);
+        let mut row = FeedbackRow {
+            agent: name.clone(),
+            branch: branch.clone(),
+            pr: None,
+            comments: 0,
+            failed: 0,
+            delivery: None,
+        };
+        let Some(fb) = host.feedback(&cwd, &branch)? else {
+            say!("{branch:<40} no PR");
+            report.push(row);
+            continue;
+        };
+        row.pr = Some(fb.pr.number);
+        match forward(&store, &snap, name, &branch, fb, &state_dir)? {
+            Some(sent) => {
+                let verb = match sent.delivery {
+                    Delivery::Delivered => "told",
+                    Delivery::Spooled => "spooled for",
+                };
+                say!(
+                    "{branch:<40} {verb} {name}: {} comment(s), {} failed check(s)",
+                    sent.comments,
+                    sent.failed
+                );
+                row.comments = sent.comments;
+                row.failed = sent.failed;
+                row.delivery = Some(sent.delivery.outcome().as_str());
+            }
+            None => say!("{branch:<40} nothing new"),
+        }
+        report.push(row);
+    }
+    if json {
+        crate::report::emit(&report)?;
+    }
+    Ok(())
+}
+
+/// `murmur pr feedback --json`: one row per branch; the counts are what
+/// was new this time.
+#[derive(Serialize)]
+pub struct FeedbackRow {
+    pub agent: String,
+    pub branch: String,
+    /// The PR number; null when the branch has none.
+    pub pr: Option<u64>,
+    pub comments: usize,
+    pub failed: usize,
+    /// delivered or spooled; null when there was nothing new.
+    pub delivery: Option<&'static str>,
+}
+
+/// What one `forward` told.
+struct Forwarded {
+    comments: usize,
+    failed: usize,
+    delivery: Delivery,
+}
+
+/// The idle-wake check: `name` just settled — pass on anything new on its
+/// branch's PR. Best-effort like the rest of the plugin.
+pub fn on_idle(store: &Store, name: &str, cwd: &Path, state_dir: &Path) {
+    let Some(snap) = store
+        .herd_of(name)
+        .ok()
+        .flatten()
+        .filter(|w| !w.slug.is_empty())
+    else {
+        return;
+    };
+    let Some(host) = Host::detect(cwd).ok().filter(|h| h.available()) else {
+        return;
+    };
+    // the seen-file's mtime is the last time we asked
+    let path = seen_path(state_dir, name);
+    let recent = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age.as_secs() < RECHECK_SECS);
+    if recent {
+        return;
+    }
+    let branch = format!("herd/{}/{name}", snap.slug);
+    match host.feedback(cwd, &branch) {
+        Ok(Some(fb)) => {
+            let _ = forward(store, &snap, name, &branch, fb, state_dir);
+        }
+        // nothing to forward, but we did ask: touch the clock
+        _ => {
+            let _ = save_seen(&path, &load_seen(&path));
+        }
+    }
+}
+
+/// Tell `name` what it hasn't heard yet; None when everything was
+/// forwarded before.
+fn forward(
+    store: &Store,
+    snap: &HerdSnap,
+    name: &str,
+    branch: &str,
+    fb: Feedback,
+    state_dir: &Path,
+) -> Result<Option<Forwarded>> {
+    let path = seen_path(state_dir, name);
+    let mut seen = load_seen(&path);
+    let comments: Vec<_> = fb
+        .comments
+        .iter()
+        .filter(|c| !seen.contains(&format!("comment:{}", c.id)))
+        .collect();
+    let failed: Vec<_> = fb
+        .failed
+        .iter()
+        .filter(|c| !seen.contains(&format!("check:{}", c.id)))
+        .collect();
+    if comments.is_empty() && failed.is_empty() {
+        save_seen(&path, &seen)?;
+        return Ok(None);
+    }
+
+    let mut body = format!(
+        "[pr feedback] #{} on {branch} ({}): {} new comment(s), {} failed check(s)",
+        fb.pr.number,
+        fb.pr.url,
+        comments.len(),
+        failed.len()
+    );
+    for c in comments.iter().take(MAX_QUOTED) {
+        let on = c
+            .path
+            .as_deref()
+            .map(|p| format!(" on {p}"))
+            .unwrap_or_default();
+        body.push_str(&format!("\n- {}{on}: {}", c.author, condense(&c.body)));
+    }
+    if comments.len() > MAX_QUOTED {
+        body.push_str(&format!(
+            "\n- … and {} more on the PR",
+            comments.len() - MAX_QUOTED
+        ));
+    }
+    if !failed.is_empty() {
+        let names: Vec<&str> = failed.iter().map(|c| c.name.as_str()).collect();
+        body.push_str(&format!("\nfailed: {}", names.join(", ")));
+    }
+    body.push_str(
+        "\nAddress them on your branch and push; if you disagree with a review, \
+         `murmur tell lead \"...\"`.",
+    );
+    let delivery = tell_or_spool(store, "murmur", name, &body)?;
+
+    let mut event = Event::new(Verb::Pr, "murmur", name, delivery.outcome());
+    event.wave = snap.key();
+    event.note = format!(
+        "feedback #{}: {} comment(s), {} failed check(s)",
+        fb.pr.number,
+        comments.len(),
+        failed.len()
+    );
+    record(store, event);
+    seen.extend(comments.iter().map(|c| format!("comment:{}", c.id)));
+    seen.extend(failed.iter().map(|c| format!("check:{}", c.id)));
+    save_seen(&path, &seen)?;
+    Ok(Some(Forwarded {
+        comments: comments.len(),
+        failed: failed.len(),
+        delivery,
+    }))
+}
+
+/// One line, and not a wall of it.
+fn condense(body: &str) -> String {
+    let line = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() > 200 {
+        format!("{}…", line.chars().take(200).collect::<String>())
+    } else {
+        line
+    }
+}
+
+fn seen_path(state_dir: &Path, name: &str) -> PathBuf {
+    state_dir.join(format!("feedback-{name}.json"))
+}
+
+fn load_seen(path: &Path) -> HashSet<String> {
+    std::fs::read(path)
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default()
+}
+
+fn save_seen(path: &Path, seen: &HashSet<String>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_vec(seen)?)?;
+    Ok(())
+}
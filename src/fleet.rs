@@ -42,16 +42,7 @@ Cloud kinds run on the provider's VM and come back as PRs (needs CURSOR_API_KEY)
 
 /// Nearest FLEET.md walking up from cwd, like AGENTS.md and .murmur.
 pub fn find() -> Option<PathBuf> {
-    let mut dir = std::env::current_dir().ok()?;
-    loop {
-        let candidate = dir.join(FILE);
-        if candidate.is_file() {
-            return Some(candidate);
-        }
-        if !dir.pop() {
-            return None;
-        }
-    }
+    crate::store::find_up(FILE)
 }
 
 /// The roster as brief text: trimmed, capped, None when absent or empty.
@@ -152,7 +143,25 @@ pub fn usage_line() -> Option<String> {
 }
 
 /// `murmur fleet` — the roster plus what this machine actually launched.
-pub fn show() -> Result<()> {
+pub fn show(json: bool) -> Result<()> {
+    if json {
+        // {"roster": path|null, "usage": {"24h": {kind: starts}, "7d": {...}}}
+        let usage: serde_json::Map<String, serde_json::Value> =
+            [("24h", 86_400u64), ("7d", 604_800)]
+                .iter()
+                .map(|(label, secs)| {
+                    let counts: serde_json::Map<String, serde_json::Value> = usage_since(*secs)
+                        .into_iter()
+                        .map(|(k, n)| (k, n.into()))
+                        .collect();
+                    (label.to_string(), counts.into())
+                })
+                .collect();
+        return crate::report::emit(&serde_json::json!({
+            "roster": find().map(|p| p.display().to_string()),
+            "usage": usage,
+        }));
+    }
     match find() {
         Some(path) => println!("roster {}", path.display()),
         None => println!("roster FLEET.md not found — `murmur setup` seeds one"),
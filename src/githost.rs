@@ -0,0 +1,678 @@
+//! Git hosts — the PR side of the merge queue, behind one small surface.
+//!
+//! `murmur pr` and restack's hold-on-red-checks need three things from
+//! wherever the repo lives: the PR for a branch (number, state, check
+//! counts), a way to open one, and its review feedback (comments and the
+//! names of failed checks). Each host is one match arm over its own CLI
+//! — `gh` (GitHub), `glab` (GitLab), `tea` (Gitea/Forgejo) — normalized
+//! into one `Pr`. The host comes from `git config murmur.host` when set,
+//! else the origin URL. Like herdr and bd, each CLI can be swapped for a
+//! stub (`MURMUR_GH`, `MURMUR_GLAB`, `MURMUR_TEA`).
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::restack::git_out;
+
+/// PRs per `tea pulls list` page.
+const TEA_PAGE: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Host {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+/// One PR (or merge request), whatever the host calls it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Pr {
+    pub number: u64,
+    /// open, merged, or closed
+    pub state: String,
+    pub url: String,
+    pub failing: u32,
+    pub pending: u32,
+    pub passing: u32,
+}
+
+impl Pr {
+    /// "#123 open, 2 check(s) failing"
+    pub fn note(&self) -> String {
+        let checks = match (self.failing, self.pending) {
+            (0, 0) if self.passing > 0 => "checks green".to_string(),
+            (0, 0) => "no checks".to_string(),
+            (f, 0) => format!("{f} check(s) failing"),
+            (0, p) => format!("{p} check(s) pending"),
+            (f, p) => format!("{f} failing, {p} pending"),
+        };
+        format!("#{} {}, {checks}", self.number, self.state)
+    }
+
+    pub fn open(&self) -> bool {
+        self.state == "open"
+    }
+}
+
+/// A review comment or note on a PR, by whoever left it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// The host's id for it — what feedback dedups on.
+    pub id: String,
+    pub author: String,
+    /// The file an inline comment sits on.
+    pub path: Option<String>,
+    pub body: String,
+}
+
+/// A failed check (or CI job). `id` names this run of it, so the same
+/// check failing again after a push is news again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub id: String,
+    pub name: String,
+}
+
+/// What reviewers and CI have said on a branch's PR.
+#[derive(Debug, Clone)]
+pub struct Feedback {
+    pub pr: Pr,
+    pub comments: Vec<Comment>,
+    pub failed: Vec<Check>,
+}
+
+impl Host {
+    /// The host for the repo at `cwd`: `git config murmur.host`, else
+    /// guessed from the origin URL.
+    pub fn detect(cwd: &Path) -> Result<Host> {
+        if let Ok(name) = git_out(cwd, &["config", "--get", "murmur.host"]) {
+            return Host::named(&name);
+        }
+        let url = git_out(cwd, &["remote", "get-url", "origin"]).context(
+            "no origin remote to tell the git host from — `git config murmur.host gh|glab|tea`",
+        )?;
+        Host::from_url(&url).with_context(|| {
+            format!(
+                "can't tell the git host from origin {url} — `git config murmur.host gh|glab|tea`"
+            )
+        })
+    }
+
+    pub fn named(name: &str) -> Result<Host> {
+        match name.trim().to_lowercase().as_str() {
+            "gh" | "github" => Ok(Host::GitHub),
+            "glab" | "gitlab" => Ok(Host::GitLab),
+            "tea" | "gitea" | "forgejo" | "codeberg" => Ok(Host::Gitea),
+            other => bail!("unknown git host '{other}' (supported: gh, glab, tea)"),
+        }
+    }
+
+    pub fn from_url(url: &str) -> Option<Host> {
+        let url = url.to_lowercase();
+        if url.contains("github") {
+            Some(Host::GitHub)
+        } else if url.contains("gitlab") {
+            Some(Host::GitLab)
+        } else if ["gitea", "forgejo", "codeberg"]
+            .iter()
+            .any(|h| url.contains(h))
+        {
+            Some(Host::Gitea)
+        } else {
+            None
+        }
+    }
+
+    /// The CLI this host is driven through.
+    pub fn cli(&self) -> &'static str {
+        match self {
+            Host::GitHub => "gh",
+            Host::GitLab => "glab",
+            Host::Gitea => "tea",
+        }
+    }
+
+    pub fn bin(&self) -> PathBuf {
+        let var = match self {
+            Host::GitHub => "MURMUR_GH",
+            Host::GitLab => "MURMUR_GLAB",
+            Host::Gitea => "MURMUR_TEA",
+        };
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(self.cli()))
+    }
+
+    /// Installed (or stubbed) on this machine.
+    pub fn available(&self) -> bool {
+        let bin = self.bin();
+        bin.components().count() > 1 || crate::store::on_path(self.cli())
+    }
+
+    /// The newest PR whose head is `branch`, in any state.
+    pub fn pr(&self, cwd: &Path, branch: &str) -> Result<Option<Pr>> {
+        match self {
+            Host::GitHub => {
+                let v = self.call_json(
+                    cwd,
+                    &[
+                        "pr",
+                        "list",
+                        "--head",
+                        branch,
+                        "--state",
+                        "all",
+                        "--json",
+                        "number,state,url,statusCheckRollup",
+                        "--limit",
+                        "1",
+                    ],
+                )?;
+                Ok(v.as_array().and_then(|a| a.first()).and_then(parse_gh))
+            }
+            Host::GitLab => {
+                let v = self.call_json(
+                    cwd,
+                    &[
+                        "mr",
+                        "list",
+                        "--source-branch",
+                        branch,
+                        "--all",
+                        "--output",
+                        "json",
+                    ],
+                )?;
+                Ok(v.as_array().and_then(|a| a.first()).and_then(parse_glab))
+            }
+            Host::Gitea => {
+                // tea can't filter by head: page through, newest first
+                let limit = TEA_PAGE.to_string();
+                let mut page = 1;
+                loop {
+                    let v = self.call_json(
+                        cwd,
+                        &[
+                            "pulls",
+                            "list",
+                            "--state",
+                            "all",
+                            "--output",
+                            "json",
+                            "--fields",
+                            "index,state,head,url",
+                            "--limit",
+                            &limit,
+                            "--page",
+                            &page.to_string(),
+                        ],
+                    )?;
+                    let list = v.as_array().map(|a| a.as_slice()).unwrap_or_default();
+                    let found = list
+                        .iter()
+                        .filter(|p| p.get("head").and_then(|h| h.as_str()) == Some(branch))
+                        .find_map(parse_tea);
+                    if found.is_some() || list.len() < TEA_PAGE {
+                        return Ok(found);
+                    }
+                    page += 1;
+                }
+            }
+        }
+    }
+
+    /// Open a PR from `head` (onto `base`, else the host's default) and
+    /// return its URL.
+    pub fn create(
+        &self,
+        cwd: &Path,
+        head: &str,
+        base: Option<&str>,
+        title: &str,
+        body: &str,
+    ) -> Result<String> {
+        let mut args: Vec<&str> = match self {
+            Host::GitHub => vec![
+                "pr", "create", "--head", head, "--title", title, "--body", body,
+            ],
+            Host::GitLab => vec![
+                "mr",
+                "create",
+                "--source-branch",
+                head,
+                "--title",
+                title,
+                "--description",
+                body,
+                "--yes",
+            ],
+            Host::Gitea => vec![
+                "pulls",
+                "create",
+                "--head",
+                head,
+                "--title",
+                title,
+                "--description",
+                body,
+            ],
+        };
+        if let Some(base) = base {
+            args.push(match self {
+                Host::GitLab => "--target-branch",
+                _ => "--base",
+            });
+            args.push(base);
+        }
+        let out = self.call(cwd, &args)?;
+        // the URL is the last thing each CLI prints that looks like one
+        Ok(out
+            .split_whitespace()
+            .rev()
+            .find(|w| w.starts_with("http"))
+            .unwrap_or(out.trim())
+            .to_string())
+    }
+
+    /// Review comments and failed checks on `branch`'s PR, or None when it
+    /// has none. tea has no way to list either, so Gitea says so.
+    pub fn feedback(&self, cwd: &Path, branch: &str) -> Result<Option<Feedback>> {
+        match self {
+            Host::GitHub => {
+                let v = match self.call_json(
+                    cwd,
+                    &[
+                        "pr",
+                        "view",
+                        branch,
+                        "--json",
+                        "number,state,url,statusCheckRollup,reviews,comments",
+                    ],
+                ) {
+                    Ok(v) => v,
+                    Err(e) if no_pr(&e) => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+                let Some(pr) = parse_gh(&v) else {
+                    return Ok(None);
+                };
+                let inline = self.call_json(
+                    cwd,
+                    &[
+                        "api",
+                        &format!("repos/{{owner}}/{{repo}}/pulls/{}/comments", pr.number),
+                    ],
+                )?;
+                Ok(Some(Feedback {
+                    comments: gh_comments(&v, &inline),
+                    failed: gh_failed(&v),
+                    pr,
+                }))
+            }
+            Host::GitLab => {
+                let v = match self.call_json(cwd, &["mr", "view", branch, "--output", "json"]) {
+                    Ok(v) => v,
+                    Err(e) if no_pr(&e) => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+                let Some(pr) = parse_glab(&v) else {
+                    return Ok(None);
+                };
+                let notes = self.call_json(
+                    cwd,
+                    &[
+                        "api",
+                        &format!("projects/:id/merge_requests/{}/notes", pr.number),
+                    ],
+                )?;
+                let failed = match v.pointer("/head_pipeline/id").and_then(|x| x.as_u64()) {
+                    Some(pipeline) if pr.failing > 0 => glab_failed(&self.call_json(
+                        cwd,
+                        &[
+                            "api",
+                            &format!("projects/:id/pipelines/{pipeline}/jobs?scope=failed"),
+                        ],
+                    )?),
+                    _ => Vec::new(),
+                };
+                Ok(Some(Feedback {
+                    comments: glab_comments(&notes),
+                    failed,
+                    pr,
+                }))
+            }
+            Host::Gitea => {
+                bail!("tea can't list PR comments or checks — feedback needs gh or glab")
+            }
+        }
+    }
+
+    fn call(&self, cwd: &Path, args: &[&str]) -> Result<String> {
+        let out = Command::new(self.bin())
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .with_context(|| format!("failed to run '{}' — is it installed?", self.cli()))?;
+        if !out.status.success() {
+            bail!(
+                "{} {} failed: {}",
+                self.cli(),
+                args.iter().take(2).copied().collect::<Vec<_>>().join(" "),
+                String::from_utf8_lossy(&out.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+    }
+
+    fn call_json(&self, cwd: &Path, args: &[&str]) -> Result<Value> {
+        let out = self.call(cwd, args)?;
+        if out.trim().is_empty() {
+            return Ok(Value::Array(Vec::new()));
+        }
+        serde_json::from_str(&out)
+            .with_context(|| format!("{} {} returned non-JSON", self.cli(), args.join(" ")))
+    }
+}
+
+fn parse_gh(pr: &Value) -> Option<Pr> {
+    let number = pr.get("number").and_then(|x| x.as_u64())?;
+    let (mut failing, mut pending, mut passing) = (0, 0, 0);
+    for c in pr
+        .get("statusCheckRollup")
+        .and_then(|x| x.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let conclusion = c
+            .get("conclusion")
+            .or_else(|| c.get("state"))
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+        let status = c.get("status").and_then(|x| x.as_str()).unwrap_or_default();
+        match conclusion {
+            "FAILURE" | "TIMED_OUT" | "CANCELLED" | "ERROR" => failing += 1,
+            "" | "PENDING" if status != "COMPLETED" => pending += 1,
+            _ => passing += 1,
+        }
+    }
+    Some(Pr {
+        number,
+        state: pr
+            .get("state")
+            .and_then(|x| x.as_str())
+            .unwrap_or("?")
+            .to_lowercase(),
+        url: str_of(pr, "url"),
+        failing,
+        pending,
+        passing,
+    })
+}
+
+/// GitLab reports one pipeline per MR, not a check list: it counts as one.
+fn parse_glab(mr: &Value) -> Option<Pr> {
+    let number = mr.get("iid").and_then(|x| x.as_u64())?;
+    let state = match mr.get("state").and_then(|x| x.as_str()).unwrap_or("?") {
+        "opened" => "open".to_string(),
+        other => other.to_string(),
+    };
+    let pipeline = mr
+        .pointer("/head_pipeline/status")
+        .or_else(|| mr.pointer("/pipeline/status"))
+        .and_then(|x| x.as_str())
+        .unwrap_or_default();
+    let (failing, pending, passing) = match pipeline {
+        "" => (0, 0, 0),
+        "failed" | "canceled" => (1, 0, 0),
+        "success" | "skipped" | "manual" => (0, 0, 1),
+        _ => (0, 1, 0),
+    };
+    Some(Pr {
+        number,
+        state,
+        url: str_of(mr, "web_url"),
+        failing,
+        pending,
+        passing,
+    })
+}
+
+/// Did `view` fail only because the branch has no PR? Anything else is an error.
+fn no_pr(e: &anyhow::Error) -> bool {
+    let e = e.to_string();
+    e.contains("no pull requests found") || e.contains("no open merge request")
+}
+
+/// tea's listing has no check status; Gitea PRs read as "no checks".
+fn parse_tea(pr: &Value) -> Option<Pr> {
+    let number = pr
+        .get("index")
+        .and_then(|x| x.as_u64().or_else(|| x.as_str()?.parse().ok()))?;
+    Some(Pr {
+        number,
+        state: pr
+            .get("state")
+            .and_then(|x| x.as_str())
+            .unwrap_or("?")
+            .to_lowercase(),
+        url: str_of(pr, "url"),
+        failing: 0,
+        pending: 0,
+        passing: 0,
+    })
+}
+
+/// Top-level comments, reviews with something to say, and inline
+/// comments (from the REST API — `gh pr view` doesn't carry them).
+fn gh_comments(pr: &Value, inline: &Value) -> Vec<Comment> {
+    let mut out = Vec::new();
+    let items = |key: &str| {
+        pr.get(key)
+            .and_then(|x| x.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    for c in items("comments") {
+        out.push(Comment {
+            id: id_of(&c),
+            author: str_at(&c, "/author/login"),
+            path: None,
+            body: str_of(&c, "body"),
+        });
+    }
+    for r in items("reviews") {
+        let mut body = str_of(&r, "body");
+        if r.get("state").and_then(|x| x.as_str()) == Some("CHANGES_REQUESTED") {
+            body = format!("(changes requested) {body}").trim().to_string();
+        }
+        out.push(Comment {
+            id: id_of(&r),
+            author: str_at(&r, "/author/login"),
+            path: None,
+            body,
+        });
+    }
+    for c in inline.as_array().into_iter().flatten() {
+        out.push(Comment {
+            id: id_of(c),
+            author: str_at(c, "/user/login"),
+            path: c.get("path").and_then(|x| x.as_str()).map(String::from),
+            body: str_of(c, "body"),
+        });
+    }
+    out.retain(|c| !c.id.is_empty() && !c.body.is_empty());
+    out
+}
+
+fn gh_failed(pr: &Value) -> Vec<Check> {
+    pr.get("statusCheckRollup")
+        .and_then(|x| x.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|c| {
+            matches!(
+                c.get("conclusion")
+                    .or_else(|| c.get("state"))
+                    .and_then(|x| x.as_str()),
+                Some("FAILURE" | "TIMED_OUT" | "CANCELLED" | "ERROR")
+            )
+        })
+        .map(|c| {
+            let name = c
+                .get("name")
+                .or_else(|| c.get("context"))
+                .and_then(|x| x.as_str())
+                .unwrap_or("check")
+                .to_string();
+            let run = c
+                .get("detailsUrl")
+                .or_else(|| c.get("targetUrl"))
+                .and_then(|x| x.as_str())
+                .unwrap_or_default();
+            Check {
+                id: format!("{name}@{run}"),
+                name,
+            }
+        })
+        .collect()
+}
+
+/// MR notes minus GitLab's own system notes ("added 1 commit").
+fn glab_comments(notes: &Value) -> Vec<Comment> {
+    notes
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|n| !n.get("system").and_then(|x| x.as_bool()).unwrap_or(false))
+        .map(|n| Comment {
+            id: id_of(n),
+            author: str_at(n, "/author/username"),
+            path: n
+                .pointer("/position/new_path")
+                .and_then(|x| x.as_str())
+                .map(String::from),
+            body: str_of(n, "body"),
+        })
+        .filter(|c| !c.id.is_empty() && !c.body.is_empty())
+        .collect()
+}
+
+fn glab_failed(jobs: &Value) -> Vec<Check> {
+    jobs.as_array()
+        .into_iter()
+        .flatten()
+        .map(|j| Check {
+            id: id_of(j),
+            name: str_of(j, "name"),
+        })
+        .collect()
+}
+
+/// Ids come as numbers from REST and as strings from GraphQL.
+fn id_of(v: &Value) -> String {
+    match v.get("id") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn str_at(v: &Value, pointer: &str) -> String {
+    v.pointer(pointer)
+        .and_then(|x| x.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn str_of(v: &Value, key: &str) -> String {
+    v.get(key)
+        .and_then(|x| x.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn host_from_origin_url_or_name() {
+        assert_eq!(Host::from_url("git@github.com:o/r.git"), Some(Host::GitHub));
+        assert_eq!(
+            Host::from_url("https://gitlab.example.com/o/r.git"),
+            Some(Host::GitLab)
+        );
+        assert_eq!(
+            Host::from_url("https://codeberg.org/o/r"),
+            Some(Host::Gitea)
+        );
+        assert_eq!(Host::from_url("/srv/git/r.git"), None);
+        assert_eq!(Host::named("tea").unwrap(), Host::Gitea);
+        assert!(Host::named("svn").is_err());
+    }
+
+    #[test]
+    fn each_host_normalizes_to_one_pr() {
+        let gh = parse_gh(&json!({"number": 12, "state": "OPEN", "url": "u",
+        "statusCheckRollup": [
+            {"conclusion": "FAILURE", "status": "COMPLETED"},
+            {"conclusion": "", "status": "IN_PROGRESS"},
+            {"conclusion": "SUCCESS", "status": "COMPLETED"}
+        ]}))
+        .unwrap();
+        assert_eq!((gh.number, gh.state.as_str()), (12, "open"));
+        assert_eq!((gh.failing, gh.pending, gh.passing), (1, 1, 1));
+        assert_eq!(gh.note(), "#12 open, 1 failing, 1 pending");
+
+        let glab = parse_glab(&json!({"iid": 4, "state": "opened", "web_url": "w",
+            "head_pipeline": {"status": "failed"}}))
+        .unwrap();
+        assert_eq!(glab.note(), "#4 open, 1 check(s) failing");
+
+        let tea = parse_tea(&json!({"index": "9", "state": "merged", "url": "t"})).unwrap();
+        assert_eq!(tea.note(), "#9 merged, no checks");
+    }
+
+    #[test]
+    fn feedback_keeps_what_a_reviewer_said() {
+        let pr = json!({"number": 12,
+        "comments": [{"id": "IC_1", "author": {"login": "ana"}, "body": "why?"}],
+        "reviews": [
+            {"id": "PRR_1", "author": {"login": "bo"}, "body": "", "state": "APPROVED"},
+            {"id": "PRR_2", "author": {"login": "bo"}, "body": "", "state": "CHANGES_REQUESTED"}
+        ],
+        "statusCheckRollup": [
+            {"name": "lint", "conclusion": "FAILURE", "detailsUrl": "https://ci/1"},
+            {"name": "test", "conclusion": "SUCCESS"}
+        ]});
+        let inline =
+            json!([{"id": 55, "user": {"login": "cy"}, "path": "src/a.rs", "body": "nit"}]);
+        let comments = gh_comments(&pr, &inline);
+        let ids: Vec<&str> = comments.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["IC_1", "PRR_2", "55"], "a bare approval says nothing");
+        assert_eq!(comments[1].body, "(changes requested)");
+        assert_eq!(comments[2].path.as_deref(), Some("src/a.rs"));
+        let failed = gh_failed(&pr);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(
+            (failed[0].name.as_str(), failed[0].id.as_str()),
+            ("lint", "lint@https://ci/1")
+        );
+
+        let notes = json!([
+            {"id": 1, "system": true, "body": "added 1 commit", "author": {"username": "x"}},
+            {"id": 2, "system": false, "body": "rename this", "author": {"username": "dee"},
+             "position": {"new_path": "lib.rs"}}
+        ]);
+        let comments = glab_comments(&notes);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(
+            (comments[0].id.as_str(), comments[0].author.as_str()),
+            ("2", "dee")
+        );
+    }
+}
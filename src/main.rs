@@ -1,15 +1,25 @@
 mod beads;
 mod cloud;
 mod commands;
+mod config;
 mod doctor;
+mod exit;
+mod feedback;
 mod fleet;
+mod githost;
 mod herdr;
+mod progress;
+mod report;
 mod restack;
+mod retro;
 mod secrets;
 mod setup;
 mod skills;
+mod stalls;
 mod start;
 mod store;
+mod sync;
+mod watch;
 
 use clap::{Parser, Subcommand};
 
@@ -22,8 +32,9 @@ use clap::{Parser, Subcommand};
 Murmur turns a beads plan into a working herd and a merged branch. It requires
 a running herdr (panes, presence, delivery) and uses beads (bd) as the plan —
 assignment lives on the bead, completion is the bead closing. Murmur's own
-state is one small notebook: .murmur/ holds the herd snapshot, each agent's
-brief, and a spool of undelivered tells. Nothing else.
+state is one small notebook: .murmur/ holds the herd snapshots, each agent's
+brief, a spool of undelivered tells, tells owed an ack, open asks, the
+restack queue and its gate logs, and a journal of what murmur did.
 
 QUICK START:
     murmur setup                           # AGENTS.md contract + playbooks + Herdr plugin
@@ -46,7 +57,7 @@ enum Command {
     /// Say something to an agent, reliably: delivered into their pane now,
     /// or spooled for their next idle — never silently lost
     Tell {
-        /// Agent name (lead, w1, …)
+        /// Agent name (lead, w1, …), or a group: @all, @workers, @kind:<kind>
         target: String,
         /// The message (or use --brief)
         message: Option<String>,
@@ -57,19 +68,123 @@ enum Command {
         /// Who is speaking (defaults to $MURMUR_AGENT, then "human")
         #[arg(long = "as", value_name = "NAME")]
         r#as: Option<String>,
+        /// If it has to wait in the spool, deliver it ahead of everything else
+        #[arg(long)]
+        urgent: bool,
+        /// If it has to wait, drop it instead once it is this stale (90s, 15m, 2h)
+        #[arg(long, value_name = "DUR")]
+        ttl: Option<String>,
+        /// Leave these agents out of a group tell (repeat or comma-separate)
+        #[arg(long, value_name = "NAME", value_delimiter = ',')]
+        except: Vec<String>,
+        /// Block until the recipient acks (`murmur ack <id>`), or fail after DUR
+        #[arg(long, value_name = "DUR")]
+        wait_ack: Option<String>,
+        /// Attach the tail of restack's newest gate log for their branch
+        #[arg(long)]
+        gate_log: bool,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Ask an agent something and block for the answer (`murmur reply`),
+    /// printed alone on stdout
+    Ask {
+        agent: String,
+        question: String,
+        /// Give up after this long (90s, 15m; default 10m); a late reply
+        /// lands in your spool
+        #[arg(long, value_name = "DUR")]
+        timeout: Option<String>,
+        #[arg(long = "as", value_name = "NAME")]
+        r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Answer a `murmur ask`: `murmur reply <id> "..."`
+    Reply {
+        /// Question id from the [question] prompt
+        id: String,
+        answer: String,
+        #[arg(long = "as", value_name = "NAME")]
+        r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Assignments nobody is moving: in progress in beads, pane not
+    /// working, no commit or activity past the threshold
+    Stalls {
+        /// Quiet this long counts as stalled (90m, 2h; default 1h)
+        #[arg(long, value_name = "DUR")]
+        after: Option<String>,
+        /// Tell each stalled worker, and the lead one summary
+        #[arg(long)]
+        nudge: bool,
+        /// Which wave (slug, label, goal bead, workspace id)
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
+        #[arg(long = "as", value_name = "NAME")]
+        r#as: Option<String>,
+    },
+    /// Confirm you read a tell: `murmur ack <id>` (the id is in its text)
+    Ack {
+        /// Message id from the tell ([m...])
+        id: String,
+        #[arg(long = "as", value_name = "NAME")]
+        r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Assign a bead to a worker: sets the bead in_progress with the agent
     /// as assignee, then hands the worker its slice as a prompt
     Assign {
         /// Bead id (bd-a1b2.1)
-        bead: String,
+        #[arg(required_unless_present = "auto")]
+        bead: Option<String>,
         /// Worker agent name
-        agent: String,
+        #[arg(required_unless_present = "auto")]
+        agent: Option<String>,
         /// Extra context delivered with the slice
         #[arg(long)]
         note: Option<String>,
         #[arg(long = "as", value_name = "NAME")]
         r#as: Option<String>,
+        /// Pair the wave's ready leaf beads with idle workers that have
+        /// nothing in progress
+        #[arg(long, conflicts_with_all = ["bead", "agent"])]
+        auto: bool,
+        /// With --auto: most beads one worker gets per run
+        #[arg(long, value_name = "N", default_value_t = 1, requires = "auto")]
+        max_per_agent: usize,
+        /// With --auto: print the pairings, assign nothing
+        #[arg(long, requires = "auto")]
+        dry_run: bool,
+        /// With --auto: which wave (slug, label, goal bead, workspace id)
+        #[arg(long, requires = "auto")]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Move a bead from one agent to another: assignee, a word to both,
+    /// the handoff note on the bead, optionally the old branch's commits
+    Reassign {
+        bead: String,
+        /// Agent that has it now
+        from: String,
+        /// Agent that takes it over
+        to: String,
+        /// Handoff note: recorded on the bead and told to both
+        #[arg(long)]
+        note: Option<String>,
+        /// Carry the old branch's commits: merge them in the new agent's
+        /// worktree, or bring them onto its branch without one
+        #[arg(long, value_enum, default_value = "none")]
+        carry: commands::Carry,
+        #[arg(long = "as", value_name = "NAME")]
+        r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Close a bead with attribution and tell the lead
     Done {
@@ -80,6 +195,8 @@ enum Command {
         note: Option<String>,
         #[arg(long = "as", value_name = "NAME")]
         r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Hand a bead back: open again, lead told to reassign
     Drop {
@@ -87,14 +204,59 @@ enum Command {
         bead: String,
         #[arg(long = "as", value_name = "NAME")]
         r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// What each agent has done in its worktree: ahead/behind the
+    /// integration branch, uncommitted changes, files touched, last commit
+    #[command(visible_alias = "progress")]
+    Diff {
+        /// Just this agent, with the files it touched listed
+        agent: Option<String>,
+        /// Which wave (slug, label, goal bead, workspace id)
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Live agents as herdr sees them, plus anything waiting in the spool
     Who {
         #[arg(long)]
         json: bool,
     },
+    /// The notebook's journal: what every verb did, filtered
+    Log {
+        /// Events where this agent acted or was acted on
+        #[arg(long)]
+        agent: Option<String>,
+        /// Events about this bead
+        #[arg(long)]
+        bead: Option<String>,
+        /// Events of this wave (its key, see `murmur status`)
+        #[arg(long)]
+        wave: Option<String>,
+        /// Only events newer than this long ago (90s, 15m, 2h, 1d)
+        #[arg(long, value_name = "DUR")]
+        since: Option<String>,
+        /// Only events older than this long ago
+        #[arg(long, value_name = "DUR")]
+        until: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
     /// The wave on one screen: herd, live agents, spool, ready frontier
-    Status,
+    Status {
+        /// Which wave (slug, goal bead, or label); defaults to the only or
+        /// most recent one
+        #[arg(long)]
+        wave: Option<String>,
+        /// Redraw a live dashboard every INTERVAL (default 2s) until
+        /// Ctrl-C; rows that changed are highlighted
+        #[arg(long, value_name = "INTERVAL", num_args = 0..=1, default_missing_value = "2s")]
+        watch: Option<String>,
+        #[arg(long, conflicts_with = "watch")]
+        json: bool,
+    },
     /// Prune old spool files and briefs (--all removes the whole .murmur dir)
     Clean {
         #[arg(long)]
@@ -102,6 +264,8 @@ enum Command {
         /// How old "stale" is, in hours
         #[arg(long, default_value_t = 24)]
         age_hours: u64,
+        #[arg(long)]
+        json: bool,
     },
     /// Secret references: pass secrets between agents without the values ever landing in context
     Secret {
@@ -122,8 +286,8 @@ enum Command {
         #[arg(long)]
         bead: Option<String>,
         /// How many agents to start (lead + workers). Default 2.
-        #[arg(long, default_value_t = 2)]
-        workers: usize,
+        #[arg(long)]
+        workers: Option<usize>,
         /// Agent kind (grok), a mixed herd: claude,codex=2 (first entry leads),
         /// or provider-hosted workers: cloud:cursor=2 (needs CURSOR_API_KEY)
         #[arg(long)]
@@ -132,6 +296,9 @@ enum Command {
         /// branch is the integration branch and only the lead merges
         #[arg(long)]
         worktree: bool,
+        /// Plain checkouts even when murmur.toml asks for worktrees
+        #[arg(long, conflicts_with = "worktree")]
+        no_worktree: bool,
         /// Give this wave its own notebook (.murmur-<name>/) so waves never mix
         #[arg(long)]
         board: Option<String>,
@@ -147,6 +314,11 @@ enum Command {
         /// etc.); MURMUR_WORKTREE_SLOT distinguishes instances
         #[arg(long, value_name = "CMD")]
         with: Option<String>,
+        /// A named set of these flags from murmur.toml ([preset.<name>])
+        #[arg(long, value_name = "NAME")]
+        preset: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Plan first: start only a lead, briefed to slice the goal into beads
     /// and summon its own workers when the plan is ready
@@ -165,36 +337,119 @@ enum Command {
         /// A path the whole herd converges on (repeatable)
         #[arg(long, value_name = "PATH")]
         hub: Vec<String>,
+        /// Take kind, board, and hubs from a murmur.toml preset
+        #[arg(long, value_name = "NAME")]
+        preset: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Lead's merge queue: merge each worker branch into the current branch,
     /// one at a time, gated by --cmd; stops with facts on the first conflict
     Restack {
-        /// Command to run after each merge (its failure stops the queue)
+        /// Command to run after each merge (or batch): a failure stops the
+        /// queue at the branch behind it (a red batch is bisected to find
+        /// it) unless --on-fail unwinds that merge and carries on
         #[arg(long, value_name = "CMD")]
         cmd: Option<String>,
+        /// Merge ungated, whatever murmur.toml's [restack] cmd says
+        #[arg(long, conflicts_with_all = ["cmd", "resume", "skip", "abort"])]
+        no_cmd: bool,
+        /// Which wave to restack (slug, goal bead, or label)
+        #[arg(long)]
+        wave: Option<String>,
+        /// Carry on after resolving the branch the queue stopped on
+        #[arg(long = "continue", conflicts_with_all = ["skip", "abort"])]
+        resume: bool,
+        /// Leave a branch (or agent) out of the queue and carry on
+        #[arg(long, value_name = "BRANCH", conflicts_with = "abort")]
+        skip: Option<String>,
+        /// Stop the restack and put HEAD back where it started
+        #[arg(long)]
+        abort: bool,
+        /// Merge only branches whose beads are all closed
+        #[arg(long)]
+        only_closed: bool,
+        /// Show the merge order (bead dependencies first) and merge nothing
+        #[arg(long, conflicts_with_all = ["resume", "skip", "abort"])]
+        plan: bool,
+        /// Forecast conflicts between every pair of branches (and hub
+        /// files touched twice) without merging anything
+        #[arg(
+            long,
+            conflicts_with_all = ["resume", "skip", "abort", "plan", "on_fail", "batch"]
+        )]
+        forecast: bool,
+        /// When the gate fails: revert or hold the merge, tell its owner,
+        /// and carry on with the rest
+        #[arg(long, value_enum, value_name = "MODE")]
+        on_fail: Option<store::OnFail>,
+        /// Merge up to N conflict-free branches at once (octopus), one gate
+        /// run per batch; a red batch is bisected to the branch that broke it
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        batch: Option<u32>,
+        #[arg(long)]
+        json: bool,
     },
-    /// One snapshot of every herd branch's PR: number, state, checks (needs gh)
+    /// Worker: merge the lead's integration branch into your own branch;
+    /// reports conflicts and tells the lead when one touches a hub
+    Sync {
+        /// Which wave (slug, goal bead, or label)
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long = "as", value_name = "NAME")]
+        r#as: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Post-wave report per agent and per kind: beads closed, drops,
+    /// reopens, conflicts, commits, assign-to-done time
+    Retro {
+        /// Which wave (slug, goal bead, or label) — live or stopped
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Herd branch PRs: a status snapshot, push and open them, or forward
+    /// their review feedback (needs gh, glab, or tea)
     Pr {
         #[command(subcommand)]
         cmd: PrCmd,
     },
     /// The fleet roster plus murmur-observed agent starts (24h / 7d)
-    Fleet,
-    /// Tear down the last wave: close its Herdr workspace, remove worktrees
+    Fleet {
+        #[arg(long)]
+        json: bool,
+    },
+    /// Tear down a wave: close its Herdr workspace, remove worktrees
     Stop {
         /// The named board whose wave to stop (see start --board)
         #[arg(long)]
         board: Option<String>,
+        /// Which wave (slug, goal bead, or label); defaults to the only or
+        /// most recent one
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
     },
     /// Can this machine run the roster right now? herdr up, kind binaries,
     /// cloud keys, one live provider probe
-    Doctor,
+    Doctor {
+        #[arg(long)]
+        json: bool,
+    },
     /// Follow up on provider-hosted agents launched by `start --kind cloud:<backend>`
     /// (a temporary adapter until herdr owns cloud agents)
     Cloud {
         #[command(subcommand)]
         cmd: CloudCmd,
     },
+    /// This repo's murmur.toml: defaults for start and restack, presets
+    Config {
+        #[command(subcommand)]
+        cmd: ConfigCmd,
+    },
     /// Herdr plugin adapter (idle-wake). Called by the plugin, not by hand.
     Herdr,
 }
@@ -215,7 +470,58 @@ enum SecretCmd {
 #[derive(Subcommand)]
 enum PrCmd {
     /// PR number, state, and check rollup for each herd branch
-    Status,
+    Status {
+        /// Which wave (slug, goal bead, or label)
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Push herd branches and open a PR for each from its bead; an open
+    /// PR is left alone (the push still updates it)
+    Open {
+        /// Whose branch (default: you, from $MURMUR_AGENT)
+        #[arg(long, conflicts_with = "all")]
+        agent: Option<String>,
+        /// Every worker's branch
+        #[arg(long)]
+        all: bool,
+        /// Target branch (default: the integration branch; the lead's PR
+        /// targets the repo default)
+        #[arg(long)]
+        base: Option<String>,
+        /// Which wave (slug, goal bead, or label)
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    /// Tell each herd branch's owner the review comments and failed checks
+    /// on its PR that it hasn't heard yet
+    Feedback {
+        /// Only this agent's branch (default: every herd branch)
+        #[arg(long)]
+        agent: Option<String>,
+        /// Which wave (slug, goal bead, or label)
+        #[arg(long)]
+        wave: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCmd {
+    /// The values start and restack would use here, and where each comes
+    /// from: a flag beats the preset, the preset beats the file, the file
+    /// beats murmur's default
+    Show {
+        /// Layer this preset over [start]
+        #[arg(long, value_name = "NAME")]
+        preset: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -231,7 +537,7 @@ enum CloudCmd {
 fn main() {
     if let Err(e) = run() {
         eprintln!("murmur: {}", e);
-        std::process::exit(1);
+        std::process::exit(exit::code(&e));
     }
 }
 
@@ -243,18 +549,94 @@ fn run() -> anyhow::Result<()> {
             message,
             brief,
             r#as,
-        } => commands::tell(&target, message, brief, r#as),
+            urgent,
+            ttl,
+            except,
+            wait_ack,
+            gate_log,
+            json,
+        } => commands::tell(
+            &target, message, brief, r#as, urgent, ttl, except, wait_ack, gate_log, json,
+        ),
+        Command::Ask {
+            agent,
+            question,
+            timeout,
+            r#as,
+            json,
+        } => commands::ask(&agent, &question, timeout, r#as, json),
+        Command::Reply {
+            id,
+            answer,
+            r#as,
+            json,
+        } => commands::reply(&id, &answer, r#as, json),
+        Command::Ack { id, r#as, json } => commands::ack(&id, r#as, json),
+        Command::Stalls {
+            after,
+            nudge,
+            wave,
+            json,
+            r#as,
+        } => stalls::run(wave, after, nudge, json, r#as),
         Command::Assign {
             bead,
             agent,
             note,
             r#as,
-        } => commands::assign(&bead, &agent, note, r#as),
-        Command::Done { bead, note, r#as } => commands::done(&bead, note, r#as),
-        Command::Drop { bead, r#as } => commands::drop_bead(&bead, r#as),
+            auto,
+            max_per_agent,
+            dry_run,
+            wave,
+            json,
+        } => match (bead, agent) {
+            (Some(bead), Some(agent)) if !auto => commands::assign(&bead, &agent, note, r#as, json),
+            _ => commands::assign_auto(wave, max_per_agent, dry_run, note, r#as, json),
+        },
+        Command::Reassign {
+            bead,
+            from,
+            to,
+            note,
+            carry,
+            r#as,
+            json,
+        } => commands::reassign(&bead, &from, &to, note, carry, r#as, json),
+        Command::Done {
+            bead,
+            note,
+            r#as,
+            json,
+        } => commands::done(&bead, note, r#as, json),
+        Command::Drop { bead, r#as, json } => commands::drop_bead(&bead, r#as, json),
+        Command::Diff { agent, wave, json } => progress::run(agent, wave, json),
         Command::Who { json } => commands::who(json),
-        Command::Status => commands::status(),
-        Command::Clean { all, age_hours } => commands::clean(all, age_hours),
+        Command::Log {
+            agent,
+            bead,
+            wave,
+            since,
+            until,
+            json,
+        } => commands::log(
+            commands::LogFilter {
+                agent,
+                bead,
+                wave,
+                since,
+                until,
+            },
+            json,
+        ),
+        Command::Status { wave, watch, json } => match watch {
+            Some(every) => watch::run(wave, store::parse_duration(&every)?.max(1)),
+            None => commands::status(wave, json),
+        },
+        Command::Clean {
+            all,
+            age_hours,
+            json,
+        } => commands::clean(all, age_hours, json),
         Command::Secret { cmd } => match cmd {
             SecretCmd::Exec { pairs, command } => commands::secret_exec(pairs, command),
         },
@@ -265,52 +647,129 @@ fn run() -> anyhow::Result<()> {
             workers,
             kind,
             worktree,
+            no_worktree,
             board,
             worktree_cmd,
             hub,
             with,
-        } => start::run(start::Opts {
-            goal,
-            bead,
-            workers,
-            kind,
-            worktree,
-            board,
-            worktree_cmd,
-            hubs: hub,
-            with,
-            plan: false,
-        }),
+            preset,
+            json,
+        } => {
+            let d = config::load()?.start_with(preset.as_deref())?;
+            start::run(start::Opts {
+                goal,
+                bead,
+                workers: workers.or(d.workers).unwrap_or(config::WORKERS),
+                kind: kind.or(d.kind),
+                worktree: !no_worktree && (worktree || d.worktree.unwrap_or(false)),
+                board: board.or(d.board),
+                worktree_cmd: worktree_cmd.or(d.worktree_cmd),
+                hubs: if hub.is_empty() {
+                    d.hubs.unwrap_or_default()
+                } else {
+                    hub
+                },
+                with: with.or(d.with),
+                plan: false,
+                json,
+            })
+        }
         Command::Plan {
             goal,
             bead,
             kind,
             board,
             hub,
-        } => start::run(start::Opts {
-            goal,
-            bead,
-            workers: 1,
-            kind,
-            worktree: false,
-            board,
-            worktree_cmd: None,
-            hubs: hub,
-            with: None,
-            plan: true,
-        }),
-        Command::Restack { cmd } => restack::run(cmd),
+            preset,
+            json,
+        } => {
+            let d = config::load()?.start_with(preset.as_deref())?;
+            start::run(start::Opts {
+                goal,
+                bead,
+                workers: 1,
+                kind: kind.or(d.kind),
+                worktree: false,
+                board: board.or(d.board),
+                worktree_cmd: None,
+                hubs: if hub.is_empty() {
+                    d.hubs.unwrap_or_default()
+                } else {
+                    hub
+                },
+                with: None,
+                plan: true,
+                json,
+            })
+        }
+        Command::Restack {
+            cmd,
+            no_cmd,
+            wave,
+            resume,
+            skip,
+            abort,
+            only_closed,
+            plan,
+            forecast,
+            on_fail,
+            batch,
+            json,
+        } => {
+            let action = if resume {
+                restack::Action::Continue
+            } else if let Some(branch) = skip {
+                restack::Action::Skip(branch)
+            } else if abort {
+                restack::Action::Abort
+            } else if plan {
+                restack::Action::Plan
+            } else if forecast {
+                restack::Action::Forecast
+            } else {
+                restack::Action::Start
+            };
+            // the file's gate starts a restack; a resumed one keeps its own.
+            // Only a restack that needs the file reads it, so a broken one
+            // doesn't stand in the way of --cmd or --no-cmd.
+            let cmd = match (&action, cmd) {
+                (restack::Action::Start, None) if !no_cmd => config::load()?.restack_cmd,
+                (_, cmd) => cmd,
+            };
+            let opts = restack::Opts {
+                cmd,
+                wave,
+                only_closed,
+                on_fail,
+                batch: batch.map(|n| n as usize),
+                json,
+            };
+            restack::run(opts, action)
+        }
+        Command::Sync { wave, r#as, json } => sync::run(wave, r#as, json),
+        Command::Retro { wave, json } => retro::run(wave, json),
         Command::Pr { cmd } => match cmd {
-            PrCmd::Status => restack::pr_status(),
+            PrCmd::Status { wave, json } => restack::pr_status(wave, json),
+            PrCmd::Open {
+                agent,
+                all,
+                base,
+                wave,
+                json,
+            } => restack::pr_open(agent, all, base, wave, json),
+            PrCmd::Feedback { agent, wave, json } => feedback::run(agent, wave, json),
         },
-        Command::Fleet => fleet::show(),
-        Command::Stop { board } => start::stop(board),
-        Command::Doctor => doctor::run(),
+        Command::Fleet { json } => fleet::show(json),
+        Command::Stop { board, wave, json } => start::stop(board, wave, json),
+        Command::Doctor { json } => doctor::run(json),
         Command::Cloud { cmd } => match cmd {
             CloudCmd::Status { id } => cloud::status(&id),
             CloudCmd::Prompt { id, text } => cloud::followup(&id, &text),
             CloudCmd::List => cloud::list(),
         },
+        Command::Config { cmd } => match cmd {
+            ConfigCmd::Show { preset, json } => config::show(preset, json),
+        },
         Command::Herdr => herdr::run(),
     }
 }
@@ -0,0 +1,154 @@
+//! `murmur diff` / `murmur progress` — what each agent has done in its tree.
+//!
+//! `murmur status` shows the panes and the ready frontier; it can't show
+//! the work. This reads every worktree the wave added (`HerdSnap.worktrees`)
+//! straight from git: how far its branch is ahead of and behind the
+//! integration branch, what sits uncommitted, which files it touched, and
+//! its last commit. The lead reviews the wave from one screen instead of
+//! visiting each pane. Read-only: nothing is fetched, merged, or told.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::exit::{self, Kind};
+use crate::fail;
+use crate::restack::git_out;
+use crate::stalls::ago;
+use crate::store::{self, HerdSnap, Store};
+
+#[derive(Serialize)]
+pub struct Progress {
+    pub agent: String,
+    pub branch: String,
+    pub path: String,
+    /// What ahead/behind count against: the integration branch, or for the
+    /// lead's own tree the commit the wave forked from.
+    pub against: String,
+    pub ahead: Option<u64>,
+    pub behind: Option<u64>,
+    /// `git status --porcelain` lines: modified, staged, and untracked.
+    pub uncommitted: usize,
+    /// Files the branch changed since it forked from `against`.
+    pub files: Vec<String>,
+    pub last_commit: String,
+    pub last_commit_secs: Option<u64>,
+}
+
+/// `murmur diff [agent]` — one row per worktree, or one agent's with the
+/// files it touched listed out.
+pub fn run(agent: Option<String>, wave: Option<String>, json: bool) -> Result<()> {
+    let store = Store::locate()?;
+    let snap = match (&wave, &agent) {
+        (None, Some(name)) => store.herd_of(name)?,
+        _ => store.herd_select(wave.as_deref())?,
+    }
+    .filter(|w| !w.worktrees.is_empty())
+    .ok_or_else(|| {
+        exit::err(
+            Kind::NotFound,
+            "no wave with worktrees — diff reads a `murmur start --worktree` herd",
+        )
+    })?;
+    let mut rows = collect(&snap);
+    if let Some(name) = &agent {
+        rows.retain(|p| p.agent == *name);
+        if rows.is_empty() {
+            fail!(NotFound, "{name} has no worktree in wave {}", snap.key());
+        }
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+    println!(
+        "{:<12} {:<24} {:>5} {:>6} {:>5} {:>5}  LAST COMMIT",
+        "AGENT", "BRANCH", "AHEAD", "BEHIND", "DIRTY", "FILES"
+    );
+    for p in &rows {
+        println!(
+            "{:<12} {:<24} {:>5} {:>6} {:>5} {:>5}  {} ({})",
+            p.agent,
+            p.branch,
+            count(p.ahead),
+            count(p.behind),
+            p.uncommitted,
+            p.files.len(),
+            p.last_commit,
+            ago(p.last_commit_secs)
+        );
+    }
+    if agent.is_some() {
+        for p in &rows {
+            println!("\n{} — {} (vs {})", p.branch, p.path, p.against);
+            for f in &p.files {
+                println!("  {f}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Every worktree of the wave that still exists, in the order start
+/// added them.
+pub fn collect(snap: &HerdSnap) -> Vec<Progress> {
+    let lead = snap.agents.first().cloned().unwrap_or_default();
+    let integration = format!("herd/{}/{lead}", snap.slug);
+    let now = store::now_millis() / 1000;
+    snap.worktrees
+        .iter()
+        .map(Path::new)
+        .filter(|p| p.is_dir())
+        .map(|path| {
+            let git = |args: &[&str]| git_out(path, args).unwrap_or_default();
+            let branch = git(&["rev-parse", "--abbrev-ref", "HEAD"]);
+            let agent = branch
+                .strip_prefix(&format!("herd/{}/", snap.slug))
+                .map(String::from)
+                .or_else(|| path.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_default();
+            let against = if branch == integration && !snap.base.is_empty() {
+                snap.base.clone()
+            } else {
+                integration.clone()
+            };
+            let (ahead, behind) = match git(&[
+                "rev-list",
+                "--left-right",
+                "--count",
+                &format!("HEAD...{against}"),
+            ])
+            .split_once('\t')
+            {
+                Some((a, b)) => (a.parse().ok(), b.parse().ok()),
+                None => (None, None),
+            };
+            let files = git(&["diff", "--name-only", &format!("{against}...HEAD")])
+                .lines()
+                .map(String::from)
+                .collect();
+            let uncommitted = git(&["status", "--porcelain"]).lines().count();
+            let last_commit = git(&["log", "-1", "--format=%s"]);
+            let last_commit_secs = git(&["log", "-1", "--format=%ct"])
+                .parse::<u64>()
+                .ok()
+                .map(|t| now.saturating_sub(t));
+            Progress {
+                agent,
+                branch,
+                path: path.display().to_string(),
+                against,
+                ahead,
+                behind,
+                uncommitted,
+                files,
+                last_commit,
+                last_commit_secs,
+            }
+        })
+        .collect()
+}
+
+fn count(n: Option<u64>) -> String {
+    n.map(|n| n.to_string()).unwrap_or_else(|| "?".into())
+}
@@ -0,0 +1,43 @@
+//! `--json` — what scripts and the lead read instead of our prose.
+//!
+//! Every verb's human output is free to change wording; its `--json` is
+//! not. A verb run with `--json` prints exactly one JSON document on
+//! stdout — its report type, below or next to the verb — and the running
+//! commentary (`merged herd/s/w1`, `tree w1 …`) moves to stderr, so the
+//! two never mix. Field names are the contract: new fields may appear,
+//! existing ones keep their name and meaning. The exit code still says
+//! whether the verb succeeded; a verb that stops partway (a restack on a
+//! conflict) prints its report first, then fails.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Route the commentary for the rest of this run: stderr under `--json`.
+pub fn set_json(on: bool) {
+    JSON.store(on, Ordering::Relaxed);
+}
+
+pub fn json() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+/// The one JSON document a `--json` run prints.
+pub fn emit<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
+/// A line of running commentary: stdout normally, stderr under `--json`.
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if $crate::report::json() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
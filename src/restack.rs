@@ -1,27 +1,141 @@
-//! The lead's merge queue — `murmur restack` and `murmur pr status`.
+//! The lead's merge queue — `murmur restack` and `murmur pr status|open`.
 //!
 //! Murmur already tells the lead "your branch is the integration branch";
 //! this module is the doing. Still userland policy, still shelling out to
-//! tools everyone has (`git`, optionally `gh`): merge each worker branch
-//! into the current checkout one at a time, gate each merge on an optional
-//! command, stop with the facts on the first conflict. No history rewrites
-//! — worker branches are other people's checkouts, so integration is
-//! merges, never rebases.
+//! tools everyone has (`git`, optionally the git host's CLI — see
+//! `githost`): merge each worker branch into the current checkout one at
+//! a time, gate each merge on an optional command, stop with the facts on
+//! the first conflict. Branches go in the plan's order — beads' `blocks`
+//! edges between agents' slices, open work last — so a slice never lands
+//! before the one it builds on, and `--forecast` simulates every pairing
+//! in memory before any of it. The queue is kept in the notebook like
+//! git's sequencer keeps `.git/sequencer`, so a stop resumes
+//! (`--continue`, `--skip`, `--abort`). No history rewrites — worker
+//! branches are other people's checkouts, so integration is merges, never
+//! rebases.
 
 use anyhow::{bail, Context, Result};
-use std::path::Path;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::store::Store;
+use crate::commands::{record, tell_or_spool};
+use crate::exit::{self, Kind};
+use crate::fail;
+use crate::githost::{Host, Pr};
+use crate::say;
+use crate::store::{
+    BranchState, Event, HerdSnap, OnFail, Outcome, QueueItem, RestackQueue, Store, Verb,
+};
 
-/// Merge every herd worker branch into the current branch, oldest herd
-/// order, gated by `cmd` when given. Run from the integration checkout
-/// (the lead's worktree).
-pub fn run(cmd: Option<String>) -> Result<()> {
+/// `murmur restack`'s knobs; the verb is an `Action`.
+pub struct Opts {
+    /// Gate run after each merge.
+    pub cmd: Option<String>,
+    pub wave: Option<String>,
+    /// Leave out branches whose beads are still open (and branches
+    /// waiting on them).
+    pub only_closed: bool,
+    /// A failed gate unwinds the merge and carries on instead of stopping.
+    pub on_fail: Option<OnFail>,
+    /// Merge up to this many conflict-free branches at once, one gate run
+    /// per batch.
+    pub batch: Option<usize>,
+    /// One `RestackReport` (or plan, or forecast) on stdout; commentary
+    /// to stderr.
+    pub json: bool,
+}
+
+/// What `murmur restack` was asked to do — git's sequencer verbs, plus a
+/// dry look at the order.
+pub enum Action {
+    /// Build a fresh queue and work through it.
+    Start,
+    /// Print the merge order and why; merge nothing.
+    Plan,
+    /// Simulate every merge in memory and print who would conflict.
+    Forecast,
+    /// The stopped branch was resolved by hand; carry on.
+    Continue,
+    /// Leave this branch (or agent) out and carry on.
+    Skip(String),
+    /// Put HEAD back where it was before the restack began.
+    Abort,
+}
+
+/// `murmur restack --json`: the queue as it stands when the run ends.
+#[derive(Serialize)]
+pub struct RestackReport<'a> {
+    pub wave: &'a str,
+    pub into: &'a str,
+    /// Where the integration branch was before the restack began.
+    pub head: &'a str,
+    pub gated: bool,
+    /// done, stopped (a conflict or a red gate; see `stop`), or aborted.
+    pub outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<String>,
+    /// Every branch in merge order: pending, merged (with its commit),
+    /// held, conflict, failed, or skipped — and why.
+    pub branches: &'a [QueueItem],
+}
+
+impl<'a> RestackReport<'a> {
+    fn of(q: &'a RestackQueue, outcome: &'static str, stop: Option<String>) -> Self {
+        RestackReport {
+            wave: &q.wave,
+            into: &q.into,
+            head: &q.head,
+            gated: q.cmd.is_some(),
+            outcome,
+            stop,
+            branches: &q.items,
+        }
+    }
+}
+
+struct Ctx<'a> {
+    store: &'a Store,
+    cwd: PathBuf,
+    me: String,
+    wave: String,
+    hubs: Vec<String>,
+    /// The git host, when its CLI is here: red PR checks hold a branch.
+    host: Option<Host>,
+}
+
+impl Ctx<'_> {
+    fn journal(&self, branch: &str, outcome: Outcome, note: &str) {
+        let mut event = Event::new(Verb::Restack, &self.me, branch, outcome);
+        event.wave = self.wave.clone();
+        event.note = note.to_string();
+        record(self.store, event);
+    }
+}
+
+/// Merge every herd worker branch of `wave` (else the only or most recent
+/// wave) into the current branch, in bead-dependency order, gated by `cmd`
+/// when given. Run from the integration checkout (the lead's worktree).
+/// The queue lives in the notebook, so a stop on a conflict or a failed
+/// gate resumes with `Action::Continue` instead of starting over. A run
+/// that lands the rest but holds a branch ends `Partial`.
+pub fn run(opts: Opts, action: Action) -> Result<()> {
+    let Opts {
+        cmd,
+        wave,
+        only_closed,
+        on_fail,
+        batch,
+        json,
+    } = opts;
+    crate::report::set_json(json);
     let store = Store::locate()?;
-    let snap = store.herd_load()?.context(
-        "no herd snapshot (herd.json) — restack serves a `murmur start --worktree` herd",
-    )?;
+    let snap = store.herd_select(wave.as_deref())?.ok_or_else(|| {
+        exit::err(
+            Kind::NotFound,
+            "no herd snapshot — restack serves a `murmur start --worktree` herd",
+        )
+    })?;
     anyhow::ensure!(
         !snap.slug.is_empty(),
         "herd snapshot has no slug — was this herd started with --worktree?"
@@ -29,101 +143,1020 @@ pub fn run(cmd: Option<String>) -> Result<()> {
     let cwd = std::env::current_dir().context("cannot determine cwd")?;
     let current = git_out(&cwd, &["rev-parse", "--abbrev-ref", "HEAD"])
         .context("restack must run inside the integration checkout")?;
-    let gh = crate::store::on_path("gh");
+    let ctx = Ctx {
+        store: &store,
+        me: crate::commands::sender(None),
+        wave: snap.key(),
+        hubs: snap.hubs.clone(),
+        host: Host::detect(&cwd).ok().filter(|h| h.available()),
+        cwd,
+    };
+    let stopped = store.queue_load(&ctx.wave)?.filter(|q| q.in_progress());
 
-    let mut merged = 0;
-    for name in &snap.agents {
-        let branch = format!("herd/{}/{}", snap.slug, name);
-        if branch == current {
-            continue;
+    let mut queue = match action {
+        Action::Plan => {
+            let plan = order(&ctx, &snap, branches(&ctx, &snap, &current), only_closed)?;
+            if json {
+                return crate::report::emit(&plan);
+            }
+            print_plan(&plan, &current);
+            return Ok(());
         }
-        if git(&cwd, &["rev-parse", "--verify", "--quiet", &branch]).is_err() {
-            continue; // cloud worker or a pane that never committed
+        Action::Forecast => {
+            let f = forecast(&ctx, branches(&ctx, &snap, &current), &current)?;
+            if json {
+                return crate::report::emit(&f);
+            }
+            print_forecast(&f);
+            return Ok(());
         }
-        let changed = git_out(
-            &cwd,
-            &["diff", "--name-only", &format!("{current}...{branch}")],
-        )
-        .unwrap_or_default();
-        if changed.is_empty() {
-            println!("skip   {branch} (no changes)");
-            continue;
+        Action::Start => {
+            if let Some(q) = stopped {
+                fail!(
+                    Refused,
+                    "a restack of {} is already under way ({}) — `murmur restack --continue`, \
+                     `--skip <branch>`, or `--abort`",
+                    q.wave,
+                    progress(&q)
+                );
+            }
+            let plan = order(&ctx, &snap, branches(&ctx, &snap, &current), only_closed)?;
+            let items = plan
+                .into_iter()
+                .map(|p| {
+                    let (state, note) = match &p.held_back {
+                        Some(why) => {
+                            say!("skip   {} ({why})", p.branch);
+                            ctx.journal(&p.branch, Outcome::Skipped, why);
+                            (BranchState::Skipped, why.clone())
+                        }
+                        None => (BranchState::Pending, String::new()),
+                    };
+                    QueueItem {
+                        branch: p.branch,
+                        state,
+                        commit: String::new(),
+                        note,
+                    }
+                })
+                .collect();
+            RestackQueue {
+                wave: ctx.wave.clone(),
+                into: current.clone(),
+                head: git_out(&ctx.cwd, &["rev-parse", "HEAD"])?,
+                cmd,
+                on_fail,
+                batch,
+                items,
+            }
+        }
+        Action::Abort => {
+            let q = stopped.ok_or_else(|| {
+                exit::err(Kind::NotFound, "no restack in progress — nothing to abort")
+            })?;
+            abort(&ctx, &q, &current)?;
+            if json {
+                crate::report::emit(&RestackReport::of(&q, "aborted", None))?;
+            }
+            return Ok(());
+        }
+        Action::Continue | Action::Skip(_) => {
+            let mut q = stopped.ok_or_else(|| {
+                exit::err(
+                    Kind::NotFound,
+                    "no restack in progress — start one with `murmur restack`",
+                )
+            })?;
+            if q.into != current {
+                fail!(
+                    Refused,
+                    "the restack merges into {}, but {current} is checked out",
+                    q.into
+                );
+            }
+            if cmd.is_some() {
+                q.cmd = cmd;
+            }
+            if on_fail.is_some() {
+                q.on_fail = on_fail;
+            }
+            if batch.is_some() {
+                q.batch = batch;
+            }
+            match action {
+                Action::Skip(which) => skip(&ctx, &mut q, &snap.slug, &which)?,
+                _ => resume(&ctx, &mut q)?,
+            }
+            q
         }
-        let hub_hits: Vec<&str> = snap
-            .hubs
+    };
+    store.queue_save(&queue)?;
+    let worked = work(&ctx, &mut queue);
+    if json {
+        let (outcome, stop) = match &worked {
+            Ok(()) => ("done", None),
+            Err(e) => ("stopped", Some(format!("{e:#}"))),
+        };
+        crate::report::emit(&RestackReport::of(&queue, outcome, stop))?;
+    }
+    worked?;
+    let merged = queue
+        .items
+        .iter()
+        .filter(|i| i.state == BranchState::Merged)
+        .count();
+    say!(
+        "restack done: {merged} branch(es) merged into {}{}",
+        queue.into,
+        if queue.cmd.is_some() { " (gated)" } else { "" }
+    );
+    report(&queue);
+    let held = queue
+        .items
+        .iter()
+        .filter(|i| i.state == BranchState::Held)
+        .count();
+    if held > 0 {
+        fail!(
+            Partial,
+            "{held} branch(es) held — fix them and run `murmur restack` again"
+        );
+    }
+    Ok(())
+}
+
+/// The wave's worker branches that exist, herd order, minus `current`.
+fn branches(ctx: &Ctx, snap: &HerdSnap, current: &str) -> Vec<String> {
+    snap.agents
+        .iter()
+        .map(|name| format!("herd/{}/{}", snap.slug, name))
+        .filter(|b| b != current)
+        // cloud workers and panes that never committed have no branch
+        .filter(|b| git(&ctx.cwd, &["rev-parse", "--verify", "--quiet", b]).is_ok())
+        .collect()
+}
+
+/// One branch's place in the merge order, and why — `--plan --json`
+/// prints the list.
+#[derive(Serialize)]
+struct Planned {
+    branch: String,
+    /// The agent's beads in this wave.
+    beads: Vec<PlanBead>,
+    /// Branches holding beads this one's beads wait on.
+    after: Vec<String>,
+    /// Why `--only-closed` leaves it out.
+    held_back: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PlanBead {
+    id: String,
+    closed: bool,
+}
+
+impl Planned {
+    fn open(&self) -> bool {
+        self.beads.iter().any(|b| !b.closed)
+    }
+}
+
+/// Order `branches` by the plan: a branch whose agent's beads are blocked
+/// by another agent's beads merges after that agent's branch, and branches
+/// with open beads go last. Each agent's beads are the goal's children it
+/// closed (or holds). Without a goal bead or beads, herd order stands; a
+/// dependency cycle falls back to herd order for what's left.
+fn order(
+    ctx: &Ctx,
+    snap: &HerdSnap,
+    branches: Vec<String>,
+    only_closed: bool,
+) -> Result<Vec<Planned>> {
+    let beads_here = !snap.bead.is_empty() && crate::beads::available_in(&ctx.cwd);
+    if !beads_here && only_closed {
+        fail!(
+            Missing,
+            "--only-closed needs beads (bd) and a wave with a goal bead"
+        );
+    }
+    let issues: Vec<crate::beads::Issue> = if beads_here {
+        crate::beads::children(&snap.bead)?
             .iter()
-            .map(|h| h.as_str())
-            .filter(|h| {
-                changed
+            .filter_map(|id| crate::beads::fetch(id).ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let owner = |i: &crate::beads::Issue| {
+        crate::beads::closer(&i.close_reason).unwrap_or_else(|| i.assignee.clone())
+    };
+    let agent_of = |b: &str| b.rsplit('/').next().unwrap_or_default().to_string();
+    let branch_of: std::collections::HashMap<&str, &String> = issues
+        .iter()
+        .filter_map(|i| {
+            let agent = owner(i);
+            branches
+                .iter()
+                .find(|b| agent_of(b) == agent)
+                .map(|b| (i.id.as_str(), b))
+        })
+        .collect();
+    let mut pending: Vec<Planned> = branches
+        .iter()
+        .map(|b| {
+            let mine: Vec<&crate::beads::Issue> =
+                issues.iter().filter(|i| owner(i) == agent_of(b)).collect();
+            let mut after: Vec<String> = Vec::new();
+            for blocker in mine.iter().flat_map(|i| &i.blockers) {
+                if let Some(other) = branch_of.get(blocker.as_str()) {
+                    if *other != b && !after.contains(other) {
+                        after.push((*other).clone());
+                    }
+                }
+            }
+            Planned {
+                branch: b.clone(),
+                beads: mine
+                    .iter()
+                    .map(|i| PlanBead {
+                        id: i.id.clone(),
+                        closed: i.closed(),
+                    })
+                    .collect(),
+                after,
+                held_back: None,
+            }
+        })
+        .collect();
+
+    // Kahn's algorithm, choosing closed-before-open then herd order.
+    let mut placed: Vec<Planned> = Vec::new();
+    while !pending.is_empty() {
+        let free = |p: &Planned| {
+            p.after
+                .iter()
+                .all(|a| placed.iter().any(|d| &d.branch == a))
+        };
+        let next = pending
+            .iter()
+            .position(|p| !p.open() && free(p))
+            .or_else(|| pending.iter().position(free))
+            .unwrap_or(0);
+        let mut p = pending.remove(next);
+        if only_closed {
+            if p.beads.is_empty() || p.open() {
+                p.held_back = Some(if p.beads.is_empty() {
+                    "no closed bead".into()
+                } else {
+                    "beads still open".into()
+                });
+            } else if let Some(dep) = p.after.iter().find(|a| {
+                placed
+                    .iter()
+                    .any(|d| &d.branch == *a && d.held_back.is_some())
+            }) {
+                p.held_back = Some(format!("waits on {dep}, held back"));
+            }
+        }
+        placed.push(p);
+    }
+    Ok(placed)
+}
+
+fn print_plan(plan: &[Planned], into: &str) {
+    say!("restack plan into {into} ({} branch(es)):", plan.len());
+    for (n, p) in plan.iter().enumerate() {
+        let beads: Vec<String> = p
+            .beads
+            .iter()
+            .map(|b| format!("{} {}", b.id, if b.closed { "closed" } else { "open" }))
+            .collect();
+        let mut why = Vec::new();
+        if !p.after.is_empty() {
+            why.push(format!("after {}", p.after.join(", ")));
+        }
+        if let Some(held) = &p.held_back {
+            why.push(format!("skip: {held}"));
+        }
+        say!(
+            "{:>2}. {:<24} {:<24} {}",
+            n + 1,
+            p.branch,
+            if beads.is_empty() {
+                "—".into()
+            } else {
+                beads.join(", ")
+            },
+            why.join("; ")
+        );
+    }
+}
+
+/// `--forecast`'s findings; `--json` prints them as is.
+#[derive(Serialize)]
+struct Forecast {
+    into: String,
+    /// Short names: the integration branch, then each agent.
+    names: Vec<String>,
+    /// Conflicting files per pair of `names` indices, both ways round.
+    #[serde(skip)]
+    clash: Vec<Vec<Vec<String>>>,
+    conflicts: Vec<Clash>,
+    /// Hubs more than one branch edits.
+    hubs: Vec<HubShare>,
+}
+
+#[derive(Serialize)]
+struct Clash {
+    a: String,
+    b: String,
+    files: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct HubShare {
+    hub: String,
+    agents: Vec<String>,
+}
+
+/// `--forecast`: every branch against `into` and every pair of branches
+/// through `git merge-tree --write-tree` — no checkout, no index, no
+/// working tree touched — plus the hub files more than one branch edits.
+/// A lead reads it to re-slice before the conflict happens.
+fn forecast(ctx: &Ctx, branches: Vec<String>, into: &str) -> Result<Forecast> {
+    let name = |b: &str| b.rsplit('/').next().unwrap_or(b).to_string();
+    let mut names = vec![into.to_string()];
+    names.extend(branches.iter().map(|b| name(b)));
+    let mut refs = vec![into.to_string()];
+    refs.extend(branches.iter().cloned());
+
+    let n = refs.len();
+    let mut clash = vec![vec![Vec::<String>::new(); n]; n];
+    let mut conflicts = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            let files = merge_conflicts(&ctx.cwd, &refs[i], &refs[j])?;
+            if !files.is_empty() {
+                conflicts.push(Clash {
+                    a: names[i].clone(),
+                    b: names[j].clone(),
+                    files: files.clone(),
+                });
+            }
+            clash[i][j] = files.clone();
+            clash[j][i] = files;
+        }
+    }
+    let mut hubs = Vec::new();
+    for hub in &ctx.hubs {
+        let touching: Vec<String> = branches
+            .iter()
+            .filter(|b| {
+                git_out(&ctx.cwd, &["diff", "--name-only", &format!("{into}...{b}")])
+                    .unwrap_or_default()
                     .lines()
-                    .any(|f| f == *h || f.starts_with(&format!("{h}/")))
+                    .any(|f| under_hub(f, hub))
             })
+            .map(|b| name(b))
             .collect();
-        if !hub_hits.is_empty() {
-            println!("hub    {branch} touches {}", hub_hits.join(", "));
-        }
-        if gh {
-            if let Some(note) = pr_note(&cwd, &branch) {
-                println!("pr     {branch}: {note}");
-                if note.contains("failing") {
-                    println!("hold   {branch} — its PR checks are failing; merge it after they're green, or run restack again to retry");
-                    continue;
+        if touching.len() > 1 {
+            hubs.push(HubShare {
+                hub: hub.clone(),
+                agents: touching,
+            });
+        }
+    }
+    Ok(Forecast {
+        into: into.to_string(),
+        names,
+        clash,
+        conflicts,
+        hubs,
+    })
+}
+
+/// The pairwise matrix, then each conflict and shared hub spelled out.
+fn print_forecast(f: &Forecast) {
+    let width = f.names.iter().map(|s| s.len()).max().unwrap_or(0).max(4) + 2;
+    print!("{:<width$}", "");
+    for col in &f.names {
+        print!("{col:<width$}");
+    }
+    println!();
+    for (i, row) in f.names.iter().enumerate() {
+        print!("{row:<width$}");
+        for (j, files) in f.clash[i].iter().enumerate() {
+            let cell = if i == j {
+                "-"
+            } else if files.is_empty() {
+                "ok"
+            } else {
+                "CONFLICT"
+            };
+            print!("{cell:<width$}");
+        }
+        println!();
+    }
+    for c in &f.conflicts {
+        println!("conflict {} x {}: {}", c.a, c.b, c.files.join(", "));
+    }
+    for h in &f.hubs {
+        println!("hub      {}: {}", h.hub, h.agents.join(", "));
+    }
+    if f.conflicts.is_empty() && f.hubs.is_empty() {
+        println!(
+            "no conflicts forecast across {} branch(es)",
+            f.names.len() - 1
+        );
+    }
+}
+
+/// Is `file` the hub path itself or inside it (a hub can be a directory)?
+fn under_hub(file: &str, hub: &str) -> bool {
+    file == hub || file.starts_with(&format!("{hub}/"))
+}
+
+/// The hubs any of `files` touches.
+pub fn hubs_touched<'a, 'f>(
+    hubs: &'a [String],
+    files: impl Iterator<Item = &'f str> + Clone,
+) -> Vec<&'a str> {
+    hubs.iter()
+        .map(|h| h.as_str())
+        .filter(|h| files.clone().any(|f| under_hub(f, h)))
+        .collect()
+}
+
+/// Files `git merge-tree` says would conflict merging `a` and `b`.
+fn merge_conflicts(cwd: &Path, a: &str, b: &str) -> Result<Vec<String>> {
+    let out = Command::new("git")
+        .args([
+            "merge-tree",
+            "--write-tree",
+            "--name-only",
+            "--no-messages",
+            a,
+            b,
+        ])
+        .current_dir(cwd)
+        .output()
+        .context("failed to run git")?;
+    match out.status.code() {
+        Some(0) => Ok(Vec::new()),
+        // first line is the (conflicted) tree, then one file per line
+        Some(1) => Ok(String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .skip(1)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_string())
+            .collect()),
+        _ => bail!(
+            "git merge-tree {a} {b} failed (--forecast needs git 2.38+): {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+    }
+}
+
+/// Work the pending branches in order — one at a time, or in batches of
+/// conflict-free branches under `--batch` — saving the queue after each.
+fn work(ctx: &Ctx, q: &mut RestackQueue) -> Result<()> {
+    loop {
+        let group = next_batch(ctx, q)?;
+        if group.is_empty() {
+            return Ok(());
+        }
+        for (branch, step) in land(ctx, q, &group)? {
+            settle(ctx, q, &branch, step)?;
+        }
+    }
+}
+
+/// Record one branch's outcome — unwinding a failed gate under
+/// `--on-fail` — and stop the queue on a conflict or a failure.
+fn settle(ctx: &Ctx, q: &mut RestackQueue, branch: &str, mut step: Step) -> Result<()> {
+    if step.state == BranchState::Failed {
+        if let Some(mode) = q.on_fail {
+            if mode == OnFail::Hold {
+                // the merge stays committed and the queue stopped on it
+                if let Err(e) = refuse_dirty(&ctx.cwd, "the failed merge is unwound") {
+                    note_step(q, branch, &step);
+                    ctx.store.queue_save(q)?;
+                    return Err(e);
                 }
             }
+            step = unwind(ctx, q, branch, mode, step)?;
+        }
+    }
+    note_step(q, branch, &step);
+    ctx.store.queue_save(q)?;
+    match step.state {
+        BranchState::Conflict => fail!(
+            Conflict,
+            "conflict merging {branch}: {}\nresolve by hand: git merge {branch}, fix, \
+             commit, then `murmur restack --continue` (or `--skip {branch}`, `--abort`)",
+            step.note
+        ),
+        BranchState::Failed => fail!(
+            Gate,
+            "{} after merging {branch} — the merge is committed; fix forward and \
+             `murmur restack --continue`, or `murmur restack --abort` to put {} back at {} \
+             (`--on-fail revert|hold` unwinds it and carries on instead)",
+            step.note,
+            q.into,
+            short(&q.head)
+        ),
+        _ => Ok(()),
+    }
+}
+
+fn note_step(q: &mut RestackQueue, branch: &str, step: &Step) {
+    if let Some(item) = q.items.iter_mut().find(|i| i.branch == branch) {
+        item.state = step.state;
+        item.note = step.note.clone();
+        item.commit = step.commit.clone();
+    }
+}
+
+/// `reset --hard` takes uncommitted work with it: refuse on a dirty tree
+/// instead, as `sync` does.
+fn refuse_dirty(cwd: &Path, before: &str) -> Result<()> {
+    let dirty = git_out(cwd, &["status", "--porcelain", "--untracked-files=no"])?;
+    if !dirty.is_empty() {
+        fail!(
+            Refused,
+            "uncommitted changes in {} — commit or stash them before {before}",
+            cwd.display()
+        );
+    }
+    Ok(())
+}
+
+/// The next branches to merge together: up to `--batch` pending branches
+/// that merge cleanly into HEAD and with each other (per `git merge-tree`).
+/// Branches with nothing new or red PR checks settle on the way; the
+/// batch ends at the first that would conflict, which goes next — alone
+/// if it conflicts with HEAD too — so the plan's order holds.
+fn next_batch(ctx: &Ctx, q: &mut RestackQueue) -> Result<Vec<String>> {
+    let size = q.batch.unwrap_or(1).max(1);
+    let pending: Vec<String> = q
+        .items
+        .iter()
+        .filter(|i| i.state == BranchState::Pending)
+        .map(|i| i.branch.clone())
+        .collect();
+    let mut group: Vec<String> = Vec::new();
+    for branch in pending {
+        if group.len() == size {
+            break;
+        }
+        if let Some(step) = precheck(ctx, q, &branch) {
+            settle(ctx, q, &branch, step)?;
+            continue;
+        }
+        if size == 1 {
+            group.push(branch);
+            continue;
+        }
+        let clean = merge_conflicts(&ctx.cwd, "HEAD", &branch)?.is_empty()
+            && group
+                .iter()
+                .all(|g| merge_conflicts(&ctx.cwd, g, &branch).is_ok_and(|c| c.is_empty()));
+        if !clean && !group.is_empty() {
+            // it goes next, on its own: nothing behind it jumps the plan
+            break;
+        }
+        group.push(branch);
+        if !clean {
+            // it conflicts with HEAD itself: merge it alone and stop there
+            break;
+        }
+    }
+    Ok(group)
+}
+
+/// Merge `group` and gate it once: a single branch is an ordinary merge,
+/// several are one octopus merge. A red batch is reset and bisected —
+/// each half landed and gated in turn — until the branch that breaks it
+/// stands alone. Returns each branch's outcome in order, ending at the
+/// first that didn't merge; the rest stay pending.
+fn land(ctx: &Ctx, q: &RestackQueue, group: &[String]) -> Result<Vec<(String, Step)>> {
+    if let [branch] = group {
+        return Ok(vec![(branch.clone(), merge_one(ctx, q, branch))]);
+    }
+    let cwd = &ctx.cwd;
+    // a batch that won't merge or gate is reset away, uncommitted work too
+    refuse_dirty(cwd, "a batch is merged")?;
+    let before = git_out(cwd, &["rev-parse", "HEAD"])?;
+    say!("batch  {}", group.join(", "));
+    let msg = format!("restack: merge {}", group.join(", "));
+    let mut args = vec!["merge", "--no-ff", "-m", &msg];
+    args.extend(group.iter().map(|b| b.as_str()));
+    if git(cwd, &args).is_err() {
+        let _ = git(cwd, &["merge", "--abort"]);
+        git(cwd, &["reset", "--hard", "-q", &before])?;
+        say!("batch  didn't merge as one — going one at a time");
+        let mut out = Vec::new();
+        for branch in group {
+            let step = merge_one(ctx, q, branch);
+            let stop = step.state != BranchState::Merged;
+            out.push((branch.clone(), step));
+            if stop {
+                break;
+            }
+        }
+        return Ok(out);
+    }
+    match gate(ctx, q, "restack-batch", &group.join(", ")) {
+        None => {
+            let commit = git_out(cwd, &["rev-parse", "HEAD"])?;
+            Ok(group
+                .iter()
+                .map(|b| {
+                    say!("merged {b}");
+                    ctx.journal(b, Outcome::Merged, "batch");
+                    let mut step = Step::new(BranchState::Merged, "");
+                    step.commit = commit.clone();
+                    (b.clone(), step)
+                })
+                .collect())
         }
-        let msg = format!("restack: merge {branch}");
-        if git(&cwd, &["merge", "--no-ff", "-m", &msg, &branch]).is_err() {
-            let conflicts =
-                git_out(&cwd, &["diff", "--name-only", "--diff-filter=U"]).unwrap_or_default();
-            let _ = git(&cwd, &["merge", "--abort"]);
-            bail!(
-                "conflict merging {branch}: {}\nresolve by hand: git merge {branch}, fix, \
-                 commit, then run `murmur restack` again for the rest",
-                conflicts.split_whitespace().collect::<Vec<_>>().join(", ")
+        Some(_) => {
+            git(cwd, &["reset", "--hard", "-q", &before])?;
+            say!(
+                "bisect {} branch(es) failed the gate together — splitting",
+                group.len()
             );
+            let (left, right) = group.split_at(group.len() / 2);
+            let mut out = land(ctx, q, left)?;
+            if out.iter().all(|(_, s)| s.state == BranchState::Merged) {
+                out.extend(land(ctx, q, right)?);
+            }
+            Ok(out)
         }
-        if let Some(cmd) = &cmd {
-            let ok = Command::new("sh")
-                .args(["-c", cmd])
-                .current_dir(&cwd)
-                .status()
-                .map(|s| s.success())
-                .unwrap_or(false);
-            if !ok {
-                bail!(
-                    "'{cmd}' failed after merging {branch} — the merge is committed; fix \
-                     forward or `git reset --hard HEAD~1` to unwind it"
-                );
+    }
+}
+
+/// What one merge attempt came to.
+struct Step {
+    state: BranchState,
+    note: String,
+    /// The merge commit, once there is one.
+    commit: String,
+    /// The end of a failed gate's output, for the branch's owner.
+    tail: String,
+}
+
+impl Step {
+    fn new(state: BranchState, note: impl Into<String>) -> Step {
+        Step {
+            state,
+            note: note.into(),
+            commit: String::new(),
+            tail: String::new(),
+        }
+    }
+}
+
+/// `--on-fail`: take the failed merge (HEAD) back out — a revert commit,
+/// or a reset to its first parent — hold the branch, and tell its owner
+/// what broke.
+fn unwind(ctx: &Ctx, q: &RestackQueue, branch: &str, mode: OnFail, failed: Step) -> Result<Step> {
+    let how = match mode {
+        OnFail::Revert => {
+            git(&ctx.cwd, &["revert", "-m", "1", "--no-edit", "HEAD"])?;
+            "reverted"
+        }
+        OnFail::Hold => {
+            git(&ctx.cwd, &["reset", "--hard", "-q", "HEAD^1"])?;
+            "unwound"
+        }
+    };
+    let note = format!("{}; {how}", failed.note);
+    say!("hold   {branch} — {note}");
+    ctx.journal(branch, Outcome::Held, &note);
+    let owner = branch.rsplit('/').next().unwrap_or(branch);
+    let body = format!(
+        "[restack] your branch {branch} failed after merging into {}: {}. The merge was {how} \
+         and your branch is held. Fix it on your branch, commit, and tell {} — the next \
+         restack picks it up.\n--- gate output (tail) ---\n{}",
+        q.into, failed.note, ctx.me, failed.tail
+    );
+    if let Err(e) = tell_or_spool(ctx.store, &ctx.me, owner, &body) {
+        eprintln!("warning: could not tell {owner}: {e:#}");
+    }
+    Ok(Step::new(BranchState::Held, note))
+}
+
+/// Before merging: a branch with nothing new is skipped, one whose PR
+/// checks are failing is held. Flags hub files on the way.
+fn precheck(ctx: &Ctx, q: &RestackQueue, branch: &str) -> Option<Step> {
+    let cwd = &ctx.cwd;
+    let changed = git_out(
+        cwd,
+        &["diff", "--name-only", &format!("{}...{branch}", q.into)],
+    )
+    .unwrap_or_default();
+    if changed.is_empty() {
+        say!("skip   {branch} (no changes)");
+        ctx.journal(branch, Outcome::Skipped, "no changes");
+        return Some(Step::new(BranchState::Skipped, "no changes"));
+    }
+    let hub_hits = hubs_touched(&ctx.hubs, changed.lines());
+    if !hub_hits.is_empty() {
+        say!("hub    {branch} touches {}", hub_hits.join(", "));
+    }
+    if let Some(host) = ctx.host {
+        if let Ok(Some(pr)) = host.pr(cwd, branch) {
+            let note = pr.note();
+            say!("pr     {branch}: {note}");
+            if pr.failing > 0 {
+                say!("hold   {branch} — its PR checks are failing; merge it after they're green, or run restack again to retry");
+                ctx.journal(branch, Outcome::Held, &note);
+                return Some(Step::new(BranchState::Held, note));
+            }
+        }
+    }
+    None
+}
+
+/// Merge one branch and gate it. Journals what happened.
+fn merge_one(ctx: &Ctx, q: &RestackQueue, branch: &str) -> Step {
+    let cwd = &ctx.cwd;
+    let msg = format!("restack: merge {branch}");
+    if git(cwd, &["merge", "--no-ff", "-m", &msg, branch]).is_err() {
+        let conflicts =
+            git_out(cwd, &["diff", "--name-only", "--diff-filter=U"]).unwrap_or_default();
+        let _ = git(cwd, &["merge", "--abort"]);
+        let files = conflicts.split_whitespace().collect::<Vec<_>>().join(", ");
+        ctx.journal(branch, Outcome::Conflict, &files);
+        return Step::new(BranchState::Conflict, files);
+    }
+    gated(ctx, q, branch)
+}
+
+/// Lines of gate output an owner hears when their branch breaks it.
+const GATE_TAIL_LINES: usize = 20;
+
+/// Gate a merged branch; Merged, or Failed with the reason (naming the
+/// log) and the end of its output. Either way the step carries HEAD.
+fn gated(ctx: &Ctx, q: &RestackQueue, branch: &str) -> Step {
+    let commit = git_out(&ctx.cwd, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let mut step = match gate(ctx, q, branch, branch) {
+        Some((note, tail)) => {
+            ctx.journal(branch, Outcome::Failed, &note);
+            Step {
+                state: BranchState::Failed,
+                note,
+                commit: String::new(),
+                tail,
             }
         }
-        merged += 1;
-        println!("merged {branch}");
+        None => {
+            say!("merged {branch}");
+            ctx.journal(branch, Outcome::Merged, "");
+            Step::new(BranchState::Merged, "")
+        }
+    };
+    step.commit = commit;
+    step
+}
+
+/// Run `--cmd` on HEAD (which has `what` merged) and keep its log under
+/// `log_as`. None when it passed or there is no gate; else the failure
+/// note and the output's tail.
+fn gate(ctx: &Ctx, q: &RestackQueue, log_as: &str, what: &str) -> Option<(String, String)> {
+    let cmd = q.cmd.as_ref()?;
+    let started = std::time::Instant::now();
+    let (code, output) = run_gate(&ctx.cwd, cmd);
+    let secs = started.elapsed().as_secs();
+    let head = git_out(&ctx.cwd, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let exit = code.map_or("signal".to_string(), |c| c.to_string());
+    let text = format!(
+        "$ {cmd}\n# {what} merged into {} at {head}\n{output}# exit {exit} after {}\n",
+        q.into,
+        crate::commands::fmt_span(secs)
+    );
+    let log = match ctx.store.gate_log_save(log_as, &text) {
+        Ok(path) => path.display().to_string(),
+        Err(e) => {
+            eprintln!("warning: could not keep the gate log: {e:#}");
+            String::new()
+        }
+    };
+    if code == Some(0) {
+        return None;
+    }
+    let mut note = format!("gate '{cmd}' failed (exit {exit})");
+    if !log.is_empty() {
+        note.push_str(&format!(", log {log}"));
+    }
+    Some((note, log_tail(&output)))
+}
+
+/// The last `GATE_TAIL_LINES` lines of a gate log — what an owner is told.
+pub fn log_tail(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(GATE_TAIL_LINES)..].join("\n")
+}
+
+/// `sh -c cmd` with stderr folded into stdout, echoed line by line as it
+/// runs and kept for the log. The exit code is None when a signal ended it.
+fn run_gate(cwd: &Path, cmd: &str) -> (Option<i32>, String) {
+    use std::io::BufRead;
+    let child = Command::new("sh")
+        .args(["-c", &format!("{{ {cmd}\n}} 2>&1")])
+        .current_dir(cwd)
+        .stdout(std::process::Stdio::piped())
+        .spawn();
+    let Ok(mut child) = child else {
+        return (None, format!("could not run sh -c '{cmd}'\n"));
+    };
+    let mut output = String::new();
+    if let Some(out) = child.stdout.take() {
+        for line in std::io::BufReader::new(out).lines().map_while(Result::ok) {
+            say!("{line}");
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    let code = child.wait().ok().and_then(|s| s.code());
+    (code, output)
+}
+
+/// `--continue`: the stopped branch must now be in HEAD — merged and
+/// committed by hand, or a merge left staged that we commit here — and
+/// passes the gate again before the queue moves on.
+fn resume(ctx: &Ctx, q: &mut RestackQueue) -> Result<()> {
+    let Some(i) = q
+        .items
+        .iter()
+        .position(|i| matches!(i.state, BranchState::Conflict | BranchState::Failed))
+    else {
+        return Ok(()); // stopped between branches: just carry on
+    };
+    let branch = q.items[i].branch.clone();
+    let cwd = &ctx.cwd;
+    if git(cwd, &["rev-parse", "-q", "--verify", "MERGE_HEAD"]).is_ok() {
+        let unmerged = git_out(cwd, &["diff", "--name-only", "--diff-filter=U"])?;
+        if !unmerged.is_empty() {
+            fail!(
+                Conflict,
+                "still unmerged: {} — fix and `git add` them, then --continue",
+                unmerged.split_whitespace().collect::<Vec<_>>().join(", ")
+            );
+        }
+        git(cwd, &["commit", "--no-edit", "-q"])?;
     }
-    println!(
-        "restack done: {merged} branch(es) merged into {current}{}",
-        if cmd.is_some() { " (gated)" } else { "" }
+    anyhow::ensure!(
+        git(cwd, &["merge-base", "--is-ancestor", &branch, "HEAD"]).is_ok(),
+        "{branch} isn't merged into {} yet — `git merge {branch}` and resolve it, or \
+         `murmur restack --skip {branch}`",
+        q.into
     );
+    let step = gated(ctx, q, &branch);
+    let item = &mut q.items[i];
+    item.state = step.state;
+    item.note = step.note;
+    item.commit = step.commit;
+    ctx.store.queue_save(q)?;
+    if step.state == BranchState::Failed {
+        fail!(
+            Gate,
+            "{} with {branch} merged — fix forward and --continue again, or --abort",
+            q.items[i].note
+        );
+    }
     Ok(())
 }
 
-/// One snapshot of every herd branch's PR: number, state, checks. The
-/// lead polls this between turns instead of babysitting the git host.
-pub fn pr_status() -> Result<()> {
+/// `--skip <branch>`: leave it out. A half-done merge of it is aborted; a
+/// merge that already committed (failed gate) stays in history.
+fn skip(ctx: &Ctx, q: &mut RestackQueue, slug: &str, which: &str) -> Result<()> {
+    let as_agent = format!("herd/{slug}/{which}");
+    let item = q
+        .items
+        .iter_mut()
+        .find(|i| i.branch == which || i.branch == as_agent)
+        .ok_or_else(|| {
+            exit::err(
+                Kind::NotFound,
+                format!("'{which}' is not in this restack's queue"),
+            )
+        })?;
     anyhow::ensure!(
-        crate::store::on_path("gh"),
-        "pr status needs the `gh` CLI on PATH"
+        matches!(
+            item.state,
+            BranchState::Pending | BranchState::Conflict | BranchState::Failed
+        ),
+        "{} is already {}",
+        item.branch,
+        item.state.as_str()
     );
+    if item.state == BranchState::Conflict
+        && git(&ctx.cwd, &["rev-parse", "-q", "--verify", "MERGE_HEAD"]).is_ok()
+    {
+        git(&ctx.cwd, &["merge", "--abort"])?;
+    }
+    if item.state == BranchState::Failed {
+        say!(
+            "note   {} stays merged — `git reset --hard HEAD~1` drops it",
+            item.branch
+        );
+    }
+    item.state = BranchState::Skipped;
+    item.note = "skipped by hand".into();
+    say!("skip   {} (by hand)", item.branch);
+    ctx.journal(&item.branch.clone(), Outcome::Skipped, "skipped by hand");
+    Ok(())
+}
+
+/// `--abort`: drop any half-done merge, reset to the pre-restack HEAD,
+/// forget the queue. Refused while other uncommitted changes are there.
+fn abort(ctx: &Ctx, q: &RestackQueue, current: &str) -> Result<()> {
+    if q.into != current {
+        fail!(
+            Refused,
+            "the restack merges into {}, but {current} is checked out",
+            q.into
+        );
+    }
+    // a half-done merge is the restack's own; anything else is yours
+    let _ = git(&ctx.cwd, &["merge", "--abort"]);
+    refuse_dirty(&ctx.cwd, "the restack is aborted")?;
+    git(&ctx.cwd, &["reset", "--hard", "-q", &q.head])?;
+    ctx.store.queue_remove(&q.wave)?;
+    ctx.journal(
+        &q.into,
+        Outcome::Aborted,
+        &format!("reset to {}", short(&q.head)),
+    );
+    say!("restack aborted: {} is back at {}", q.into, short(&q.head));
+    Ok(())
+}
+
+/// The closing word: what landed, what was held or skipped, and why.
+fn report(q: &RestackQueue) {
+    let landed: Vec<&str> = q
+        .items
+        .iter()
+        .filter(|i| i.state == BranchState::Merged)
+        .map(|i| i.branch.as_str())
+        .collect();
+    if !landed.is_empty() {
+        say!("  landed  {}", landed.join(", "));
+    }
+    for i in &q.items {
+        if matches!(i.state, BranchState::Held | BranchState::Skipped) {
+            say!("  {:<7} {} — {}", i.state.as_str(), i.branch, i.note);
+        }
+    }
+}
+
+/// "2 merged, stopped at herd/s/w2 (conflict), 1 pending"
+fn progress(q: &RestackQueue) -> String {
+    let count = |s: BranchState| q.items.iter().filter(|i| i.state == s).count();
+    let stopped = q
+        .items
+        .iter()
+        .find(|i| matches!(i.state, BranchState::Conflict | BranchState::Failed))
+        .map(|i| format!(", stopped at {} ({})", i.branch, i.state.as_str()))
+        .unwrap_or_default();
+    format!(
+        "{} merged{stopped}, {} pending",
+        count(BranchState::Merged),
+        count(BranchState::Pending)
+    )
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(10)]
+}
+
+/// One snapshot of every herd branch's PR: number, state, checks. The
+/// lead polls this between turns instead of babysitting the git host.
+pub fn pr_status(wave: Option<String>, json: bool) -> Result<()> {
     let store = Store::locate()?;
     let snap = store
-        .herd_load()?
-        .context("no herd snapshot (herd.json) — start a herd first")?;
+        .herd_select(wave.as_deref())?
+        .ok_or_else(|| exit::err(Kind::NotFound, "no herd snapshot — start a herd first"))?;
     let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let host = host_here(&cwd, "pr status")?;
+    if json {
+        // [{"agent", "branch", "pr": {number, state, url, failing, pending, passing} | null}]
+        let mut rows = Vec::new();
+        for name in &snap.agents {
+            let branch = format!("herd/{}/{}", snap.slug, name);
+            let pr = host.pr(&cwd, &branch)?;
+            rows.push(serde_json::json!({"agent": name, "branch": branch, "pr": pr}));
+        }
+        return crate::report::emit(&rows);
+    }
     let mut found = 0;
     for name in &snap.agents {
         let branch = format!("herd/{}/{}", snap.slug, name);
-        match pr_note(&cwd, &branch) {
-            Some(note) => {
-                println!("{:<40} {note}", branch);
+        match host.pr(&cwd, &branch)? {
+            Some(pr) => {
+                println!("{:<40} {}", branch, pr.note());
                 found += 1;
             }
             None => println!("{:<40} no PR", branch),
@@ -135,63 +1168,242 @@ pub fn pr_status() -> Result<()> {
     Ok(())
 }
 
-/// "#123 open, 2 checks failing" — or None when the branch has no PR.
-fn pr_note(cwd: &Path, branch: &str) -> Option<String> {
-    let out = Command::new("gh")
-        .args([
-            "pr",
-            "list",
-            "--head",
-            branch,
-            "--state",
-            "all",
-            "--json",
-            "number,state,statusCheckRollup",
-            "--limit",
-            "1",
-        ])
-        .current_dir(cwd)
-        .output()
-        .ok()?;
-    if !out.status.success() {
-        return None;
+/// The repo's git host, with its CLI installed — or why not.
+pub fn host_here(cwd: &Path, verb: &str) -> Result<Host> {
+    let host = Host::detect(cwd)?;
+    if !host.available() {
+        fail!(Missing, "{verb} needs the `{}` CLI on PATH", host.cli());
     }
-    let v: serde_json::Value = serde_json::from_slice(&out.stdout).ok()?;
-    let pr = v.as_array()?.first()?;
-    let number = pr.get("number").and_then(|x| x.as_u64())?;
-    let state = pr
-        .get("state")
-        .and_then(|x| x.as_str())
-        .unwrap_or("?")
-        .to_lowercase();
-    let checks = pr
-        .get("statusCheckRollup")
-        .and_then(|x| x.as_array())
-        .map(|arr| {
-            let mut failing = 0;
-            let mut pending = 0;
-            for c in arr {
-                let conclusion = c
-                    .get("conclusion")
-                    .and_then(|x| x.as_str())
-                    .unwrap_or_default();
-                let status = c.get("status").and_then(|x| x.as_str()).unwrap_or_default();
-                match conclusion {
-                    "FAILURE" | "TIMED_OUT" | "CANCELLED" => failing += 1,
-                    "" if status != "COMPLETED" => pending += 1,
-                    _ => {}
+    Ok(host)
+}
+
+/// `murmur pr open` — push herd branches and open a PR for each, titled
+/// and described from the agent's bead, pointing at the goal and the wave.
+/// Worker PRs target the integration branch (CI runs where restack will
+/// merge); the lead's targets the repo default. An open PR is left as it
+/// is — the push still updates it — so running it twice is harmless.
+/// `--all` carries on past a branch that won't push or open, then ends
+/// `Partial`.
+pub fn pr_open(
+    agent: Option<String>,
+    all: bool,
+    base: Option<String>,
+    wave: Option<String>,
+    json: bool,
+) -> Result<()> {
+    crate::report::set_json(json);
+    let store = Store::locate()?;
+    let snap = match (&wave, &agent) {
+        (None, Some(name)) => store.herd_of(name)?,
+        (None, None) if !all => match crate::commands::ambient(None) {
+            Some(me) => store.herd_of(&me)?,
+            None => store.herd_select(None)?,
+        },
+        _ => store.herd_select(wave.as_deref())?,
+    }
+    .filter(|w| !w.slug.is_empty())
+    .ok_or_else(|| {
+        exit::err(
+            Kind::NotFound,
+            "no worktree herd — pr open serves a `murmur start --worktree` herd",
+        )
+    })?;
+    let lead = snap.agents.first().cloned().unwrap_or_default();
+    let names: Vec<String> = if all {
+        snap.agents.iter().skip(1).cloned().collect()
+    } else {
+        let name = agent
+            .or_else(|| crate::commands::ambient(None))
+            .context("whose PR? pass --agent <name> or --all (or set MURMUR_AGENT)")?;
+        if !snap.agents.contains(&name) {
+            fail!(NotFound, "{name} is not in wave {}", snap.key());
+        }
+        vec![name]
+    };
+    let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let host = host_here(&cwd, "pr open")?;
+    let me = crate::commands::sender(None);
+    let integration = format!("herd/{}/{lead}", snap.slug);
+    let mut report = Vec::new();
+    for name in &names {
+        let branch = format!("herd/{}/{name}", snap.slug);
+        let mut row = PrOpened {
+            agent: name.clone(),
+            branch: branch.clone(),
+            outcome: "no-branch",
+            number: None,
+            url: String::new(),
+            error: None,
+        };
+        if git(&cwd, &["rev-parse", "--verify", "--quiet", &branch]).is_err() {
+            say!("{branch:<40} no branch (nothing committed?)");
+            report.push(row);
+            continue;
+        }
+        let target = match &base {
+            Some(b) => Some(b.clone()),
+            None if *name != lead => Some(integration.clone()),
+            None => None,
+        };
+        let mut event = Event::new(Verb::Pr, &me, name, Outcome::Opened);
+        event.wave = snap.key();
+        match open_one(&cwd, host, &snap, name, &branch, target.as_deref()) {
+            Ok(Opened::Already(pr)) => {
+                say!(
+                    "{branch:<40} #{} already open (pushed) {}",
+                    pr.number,
+                    pr.url
+                );
+                event.outcome = Outcome::Skipped;
+                event.note = format!("#{} already open", pr.number);
+                row.outcome = "already-open";
+                row.number = Some(pr.number);
+                row.url = pr.url;
+            }
+            Ok(Opened::New { bead, url }) => {
+                say!("{branch:<40} opened {url}");
+                event.bead = bead;
+                event.note = url.clone();
+                row.outcome = "opened";
+                row.url = url;
+            }
+            // one branch that won't push doesn't strand the rest of --all
+            Err(e) if all => {
+                eprintln!("{branch:<40} failed: {e:#}");
+                row.outcome = "failed";
+                row.error = Some(format!("{e:#}"));
+                report.push(row);
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+        record(&store, event);
+        report.push(row);
+    }
+    let count = |o: &str| report.iter().filter(|r| r.outcome == o).count();
+    let (opened, failed) = (count("opened"), count("failed"));
+    if names.len() > 1 {
+        say!("{opened} PR(s) opened of {} branch(es)", names.len());
+    }
+    if json {
+        crate::report::emit(&report)?;
+    }
+    if failed > 0 {
+        fail!(
+            Partial,
+            "{failed} of {} branch(es) could not be pushed or opened",
+            names.len()
+        );
+    }
+    Ok(())
+}
+
+/// What `open_one` found or made.
+enum Opened {
+    Already(Pr),
+    New { bead: String, url: String },
+}
+
+/// Push one branch (and the base it targets, when that's a herd branch)
+/// and open its PR unless one is already open.
+fn open_one(
+    cwd: &Path,
+    host: Host,
+    snap: &HerdSnap,
+    name: &str,
+    branch: &str,
+    target: Option<&str>,
+) -> Result<Opened> {
+    if let Some(t) = target.filter(|t| t.starts_with("herd/")) {
+        // the base must exist on the host before a PR can point at it
+        git(cwd, &["push", "-q", "origin", t]).with_context(|| format!("could not push {t}"))?;
+    }
+    git(cwd, &["push", "-q", "-u", "origin", branch])
+        .with_context(|| format!("could not push {branch}"))?;
+    if let Some(pr) = host.pr(cwd, branch)?.filter(|p| p.open()) {
+        return Ok(Opened::Already(pr));
+    }
+    let beads = slice_beads(snap, name);
+    let (title, body) = pr_text(snap, name, branch, &beads);
+    let url = host
+        .create(cwd, branch, target, &title, &body)
+        .with_context(|| format!("could not open a PR for {branch}"))?;
+    Ok(Opened::New {
+        bead: beads.first().map(|b| b.id.clone()).unwrap_or_default(),
+        url,
+    })
+}
+
+/// `murmur pr open --json`: one row per branch.
+#[derive(Serialize)]
+pub struct PrOpened {
+    pub agent: String,
+    pub branch: String,
+    /// opened, already-open, no-branch, or failed.
+    pub outcome: &'static str,
+    /// Known for a PR that was already open; a new one has only its URL.
+    pub number: Option<u64>,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The agent's beads in this wave: what it holds now, then what it
+/// closed under the goal.
+fn slice_beads(snap: &HerdSnap, agent: &str) -> Vec<crate::beads::Issue> {
+    if !crate::beads::available() {
+        return Vec::new();
+    }
+    let mut beads: Vec<crate::beads::Issue> = crate::beads::in_progress()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|i| i.assignee == agent)
+        .collect();
+    if !snap.bead.is_empty() {
+        for id in crate::beads::children(&snap.bead).unwrap_or_default() {
+            if beads.iter().any(|b| b.id == id) {
+                continue;
+            }
+            if let Ok(issue) = crate::beads::fetch(&id) {
+                let owner = crate::beads::closer(&issue.close_reason)
+                    .unwrap_or_else(|| issue.assignee.clone());
+                if issue.closed() && owner == agent {
+                    beads.push(issue);
                 }
             }
-            (failing, pending)
-        })
-        .unwrap_or((0, 0));
-    let checks_note = match checks {
-        (0, 0) => "checks green".to_string(),
-        (f, 0) => format!("{f} check(s) failing"),
-        (0, p) => format!("{p} check(s) pending"),
-        (f, p) => format!("{f} failing, {p} pending"),
+        }
+    }
+    beads
+}
+
+/// Title and body: the first bead names the PR, every bead is listed, and
+/// the goal and wave tie it back to the plan.
+fn pr_text(
+    snap: &HerdSnap,
+    agent: &str,
+    branch: &str,
+    beads: &[crate::beads::Issue],
+) -> (String, String) {
+    let title = match beads.first() {
+        Some(b) => format!("{}: {}", b.id, b.title),
+        None => format!("{}: {agent}'s slice", snap.label),
     };
-    Some(format!("#{number} {state}, {checks_note}"))
+    let mut body = String::new();
+    if let Some(b) = beads.first().filter(|b| !b.body.is_empty()) {
+        body.push_str(&b.body);
+        body.push_str("\n\n");
+    }
+    for b in beads {
+        body.push_str(&format!("- Bead: {} — {} ({})\n", b.id, b.title, b.status));
+    }
+    if !snap.bead.is_empty() {
+        body.push_str(&format!("- Goal: {}\n", snap.bead));
+    }
+    body.push_str(&format!(
+        "- Wave: {} — {agent} on `{branch}`, opened by murmur\n",
+        snap.label
+    ));
+    (title, body)
 }
 
 fn git(cwd: &Path, args: &[&str]) -> Result<()> {
@@ -206,7 +1418,7 @@ fn git(cwd: &Path, args: &[&str]) -> Result<()> {
     Ok(())
 }
 
-fn git_out(cwd: &Path, args: &[&str]) -> Result<String> {
+pub fn git_out(cwd: &Path, args: &[&str]) -> Result<String> {
     let out = Command::new("git")
         .args(args)
         .current_dir(cwd)
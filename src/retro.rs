@@ -0,0 +1,381 @@
+//! `murmur retro` — the post-wave report, joined from what already exists.
+//!
+//! Nothing new is recorded for it. The roster comes from the herd snapshot
+//! (or, once the wave is stopped, from the journal's start events); who
+//! closed what comes from beads — `murmur done` writes the closer into the
+//! close reason; drops, conflicts, and assign-to-done times come from the
+//! journal; commit counts from `git log` of the `herd/<slug>/*` branches.
+//! FLEET.md says the ledger should correct the roster: this is the ledger,
+//! per agent and per kind. It never edits the roster itself.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::exit::{self, Kind};
+use crate::fail;
+use crate::store::{Event, Outcome, Store, Verb};
+
+#[derive(Serialize)]
+pub struct Retro {
+    pub wave: String,
+    pub bead: String,
+    pub agents: Vec<AgentRow>,
+    pub kinds: Vec<KindRow>,
+    pub beads: Vec<BeadRow>,
+}
+
+#[derive(Serialize, Default)]
+pub struct AgentRow {
+    pub name: String,
+    pub kind: String,
+    pub closed: Vec<String>,
+    pub dropped: usize,
+    /// Closed by this agent, open again now.
+    pub reopened: usize,
+    pub conflicts: usize,
+    pub held: usize,
+    pub commits: usize,
+    /// Mean seconds from assign to done over the beads it closed.
+    pub assign_to_done_secs: Option<u64>,
+}
+
+#[derive(Serialize, Default)]
+pub struct KindRow {
+    pub kind: String,
+    pub agents: usize,
+    pub closed: usize,
+    pub dropped: usize,
+    pub reopened: usize,
+    pub conflicts: usize,
+    pub held: usize,
+    pub commits: usize,
+    pub assign_to_done_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct BeadRow {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub closed_by: String,
+    pub assign_to_done_secs: Option<u64>,
+}
+
+/// Who was in the wave, from the live snapshot or the journal.
+struct Roster {
+    wave: String,
+    bead: String,
+    slug: String,
+    /// The fork point of the herd branches, when the snapshot knows it.
+    base: String,
+    /// unix millis; without a base, commits before it aren't the wave's
+    started: u64,
+    agents: Vec<(String, String)>,
+}
+
+/// `murmur retro` — print the report for `wave` (else the only or most
+/// recent one, live or stopped) as markdown, or JSON with `--json`.
+pub fn run(wave: Option<String>, json: bool) -> Result<()> {
+    let store = Store::locate()?;
+    let journal = store.journal_read();
+    let roster = roster(&store, &journal, wave.as_deref())?;
+    let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let retro = build(&roster, &journal, &cwd);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&retro)?);
+    } else {
+        print!("{}", markdown(&retro));
+    }
+    Ok(())
+}
+
+fn roster(store: &Store, journal: &[Event], sel: Option<&str>) -> Result<Roster> {
+    let live = match store.herd_select(sel) {
+        Ok(snap) => snap,
+        // a stopped wave is gone from herds/ but not from the journal
+        Err(_) if sel.is_some_and(|w| journal.iter().any(|e| e.wave == w)) => None,
+        Err(e) => return Err(e),
+    };
+    if let Some(snap) = live {
+        let key = snap.key();
+        let started = journal
+            .iter()
+            .find(|e| e.verb == Verb::Start && e.wave == key)
+            .map(|e| e.ts)
+            .unwrap_or(snap.started * 1000);
+        return Ok(Roster {
+            agents: snap
+                .agents
+                .iter()
+                .map(|a| (a.clone(), snap.kind_of(a).unwrap_or("?").to_string()))
+                .collect(),
+            wave: key,
+            bead: snap.bead,
+            slug: snap.slug,
+            base: snap.base,
+            started,
+        });
+    }
+    let wave = match sel {
+        Some(w) => w.to_string(),
+        None => journal
+            .iter()
+            .rev()
+            .find(|e| e.verb == Verb::Start && !e.wave.is_empty())
+            .map(|e| e.wave.clone())
+            .ok_or_else(|| {
+                exit::err(
+                    Kind::NotFound,
+                    "no wave to report on — nothing in the notebook or its journal",
+                )
+            })?,
+    };
+    let starts: Vec<&Event> = journal
+        .iter()
+        .filter(|e| e.verb == Verb::Start && e.wave == wave)
+        .collect();
+    if starts.is_empty() {
+        fail!(NotFound, "no start recorded for wave '{wave}'");
+    }
+    Ok(Roster {
+        bead: starts[0].bead.clone(),
+        slug: wave.clone(),
+        base: String::new(),
+        started: starts[0].ts,
+        agents: starts
+            .iter()
+            .map(|e| {
+                let kind = e.note.split_whitespace().next().unwrap_or("?");
+                (e.target.clone(), kind.to_string())
+            })
+            .collect(),
+        wave,
+    })
+}
+
+fn build(roster: &Roster, journal: &[Event], cwd: &Path) -> Retro {
+    let events: Vec<&Event> = journal.iter().filter(|e| e.wave == roster.wave).collect();
+
+    // Every bead the wave touched: the goal's children plus anything the
+    // journal saw assigned, closed, or dropped.
+    let mut ids: BTreeSet<String> = events
+        .iter()
+        .filter(|e| !e.bead.is_empty() && e.bead != roster.bead)
+        .map(|e| e.bead.clone())
+        .collect();
+    let beads_here = crate::beads::available();
+    if beads_here && !roster.bead.is_empty() {
+        ids.extend(crate::beads::children(&roster.bead).unwrap_or_default());
+    }
+
+    let mut beads = Vec::new();
+    for id in &ids {
+        let issue = beads_here.then(|| crate::beads::fetch(id).ok()).flatten();
+        let done = events
+            .iter()
+            .rev()
+            .find(|e| e.verb == Verb::Done && &e.bead == id);
+        let closed_by = issue
+            .as_ref()
+            .filter(|i| i.closed())
+            .and_then(|i| crate::beads::closer(&i.close_reason).or(Some(i.assignee.clone())))
+            .or_else(|| done.map(|e| e.actor.clone()))
+            .unwrap_or_default();
+        let assign_to_done_secs = done.and_then(|d| {
+            events
+                .iter()
+                .rev()
+                .find(|e| e.verb == Verb::Assign && &e.bead == id && e.ts <= d.ts)
+                .map(|a| (d.ts - a.ts) / 1000)
+        });
+        beads.push(BeadRow {
+            id: id.clone(),
+            title: issue.as_ref().map(|i| i.title.clone()).unwrap_or_default(),
+            status: issue
+                .as_ref()
+                .map(|i| i.status.clone())
+                .unwrap_or_else(|| if done.is_some() { "closed" } else { "?" }.into()),
+            closed_by,
+            assign_to_done_secs,
+        });
+    }
+
+    let mut agents = Vec::new();
+    for (name, kind) in &roster.agents {
+        let branch = format!("herd/{}/{name}", roster.slug);
+        let restacks = |o: Outcome| {
+            events
+                .iter()
+                .filter(|e| e.verb == Verb::Restack && e.target == branch && e.outcome == o)
+                .count()
+        };
+        let closed: Vec<&BeadRow> = beads.iter().filter(|b| &b.closed_by == name).collect();
+        let reopened = events
+            .iter()
+            .filter(|e| e.verb == Verb::Done && &e.actor == name)
+            .map(|e| e.bead.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|id| {
+                beads
+                    .iter()
+                    .any(|b| b.id == *id && b.status != "closed" && b.status != "done")
+            })
+            .count();
+        agents.push(AgentRow {
+            name: name.clone(),
+            kind: kind.clone(),
+            closed: closed.iter().map(|b| b.id.clone()).collect(),
+            dropped: events
+                .iter()
+                .filter(|e| e.verb == Verb::Drop && &e.actor == name)
+                .count(),
+            reopened,
+            conflicts: restacks(Outcome::Conflict),
+            held: restacks(Outcome::Held),
+            commits: branch_commits(cwd, &branch, &roster.base, roster.started / 1000),
+            assign_to_done_secs: mean(closed.iter().filter_map(|b| b.assign_to_done_secs)),
+        });
+    }
+
+    let mut by_kind: BTreeMap<String, (KindRow, Vec<u64>)> = BTreeMap::new();
+    for a in &agents {
+        let (k, spans) = by_kind.entry(a.kind.clone()).or_default();
+        k.kind = a.kind.clone();
+        k.agents += 1;
+        k.closed += a.closed.len();
+        k.dropped += a.dropped;
+        k.reopened += a.reopened;
+        k.conflicts += a.conflicts;
+        k.held += a.held;
+        k.commits += a.commits;
+        spans.extend(
+            beads
+                .iter()
+                .filter(|b| b.closed_by == a.name)
+                .filter_map(|b| b.assign_to_done_secs),
+        );
+    }
+    let kinds = by_kind
+        .into_values()
+        .map(|(mut k, spans)| {
+            k.assign_to_done_secs = mean(spans.into_iter());
+            k
+        })
+        .collect();
+
+    Retro {
+        wave: roster.wave.clone(),
+        bead: roster.bead.clone(),
+        agents,
+        kinds,
+        beads,
+    }
+}
+
+/// First-parent commits on the agent's branch past the herd's fork point
+/// (else since the wave started) — merges the branch took in from
+/// elsewhere don't count as its work.
+fn branch_commits(cwd: &Path, branch: &str, base: &str, since_secs: u64) -> usize {
+    let range = if base.is_empty() {
+        branch.to_string()
+    } else {
+        format!("{base}..{branch}")
+    };
+    let Ok(out) = crate::restack::git_out(cwd, &["log", "--first-parent", "--format=%ct", &range])
+    else {
+        return 0;
+    };
+    out.lines()
+        .filter_map(|l| l.trim().parse::<u64>().ok())
+        .filter(|t| !base.is_empty() || *t >= since_secs)
+        .count()
+}
+
+fn mean(xs: impl Iterator<Item = u64>) -> Option<u64> {
+    let (sum, n) = xs.fold((0u64, 0u64), |(s, n), x| (s + x, n + 1));
+    (n > 0).then(|| sum / n)
+}
+
+fn span(secs: Option<u64>) -> String {
+    secs.map(crate::commands::fmt_span)
+        .unwrap_or_else(|| "—".into())
+}
+
+fn markdown(r: &Retro) -> String {
+    let mut out = format!(
+        "# Retro: {}{}\n\n## By agent\n\n\
+         | agent | kind | closed | dropped | reopened | conflicts | held | commits | assign→done |\n\
+         | --- | --- | --- | --- | --- | --- | --- | --- | --- |\n",
+        r.wave,
+        if r.bead.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", r.bead)
+        }
+    );
+    for a in &r.agents {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            a.name,
+            a.kind,
+            if a.closed.is_empty() {
+                "—".to_string()
+            } else {
+                a.closed.join(", ")
+            },
+            a.dropped,
+            a.reopened,
+            a.conflicts,
+            a.held,
+            a.commits,
+            span(a.assign_to_done_secs)
+        ));
+    }
+    out.push_str(
+        "\n## By kind\n\n\
+         | kind | agents | closed | dropped | reopened | conflicts | held | commits | assign→done |\n\
+         | --- | --- | --- | --- | --- | --- | --- | --- | --- |\n",
+    );
+    for k in &r.kinds {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            k.kind,
+            k.agents,
+            k.closed,
+            k.dropped,
+            k.reopened,
+            k.conflicts,
+            k.held,
+            k.commits,
+            span(k.assign_to_done_secs)
+        ));
+    }
+    if !r.beads.is_empty() {
+        out.push_str(
+            "\n## Beads\n\n\
+             | bead | title | status | closed by | assign→done |\n\
+             | --- | --- | --- | --- | --- |\n",
+        );
+        for b in &r.beads {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                b.id,
+                b.title,
+                b.status,
+                if b.closed_by.is_empty() {
+                    "—"
+                } else {
+                    &b.closed_by
+                },
+                span(b.assign_to_done_secs)
+            ));
+        }
+    }
+    out.push_str(
+        "\nFLEET.md is the roster; if this ledger disagrees with it, correct the roster.\n",
+    );
+    out
+}
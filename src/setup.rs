@@ -6,6 +6,7 @@
 //! protocol. Everything merges idempotently; existing files are never
 //! clobbered.
 
+use crate::fail;
 use crate::store::on_path;
 use anyhow::{Context, Result};
 use std::env;
@@ -119,7 +120,7 @@ fn murmur_exe() -> Result<PathBuf> {
 
 fn link_herdr_plugin(dir: &Path) -> Result<bool> {
     if !on_path("herdr") && std::env::var_os("MURMUR_HERDR").is_none() {
-        anyhow::bail!("herdr is not on PATH");
+        fail!(Missing, "herdr is not on PATH");
     }
     let listed = crate::herdr::call(&["plugin", "list"]).unwrap_or(serde_json::json!({}));
     let blob = listed.to_string();
@@ -170,6 +171,10 @@ fn agents_md_section() -> String {
         what your lead assigns. Do not grab beads on your own.\n\
         - **Talk with `murmur tell <agent> \\\"...\\\"`** — it delivers into their pane\n\
         now, or spools for their next idle. Never assume silence means absence.\n\
+        - **Ack what you read.** Every tell carries an id (`from lead [m1x2y3]: ...`);\n\
+        once you have read it, run `murmur ack <id>` so the sender knows it landed.\n\
+        A `[question]` is someone blocked on you: answer it with\n\
+        `murmur reply <id> \\\"...\\\"`.\n\
         - **Finish with `murmur done <bead> --note \\\"what changed\\\"`** — it closes the\n\
         bead with attribution and tells the lead. Can't finish?\n\
         `murmur drop <bead>` hands it back.\n\
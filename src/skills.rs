@@ -45,15 +45,39 @@ Workers own exactly the slice you hand them. Do not wait for the human.
 - Assign every slice: `murmur assign <bead> <worker> --note "..."`.
   The bead carries the assignment (in_progress + assignee) and the worker
   hears the slice as a prompt. One bead, one worker, one owner.
+- Many ready slices, many idle workers? `murmur assign --auto --dry-run`
+  shows the pairing it would make (ready leaves under your goal, idle
+  workers with nothing in progress); drop `--dry-run` to assign. Your
+  hand assignments always win.
 - `murmur done <bead>` closes a slice; workers close their own and you
   hear it. `murmur drop <bead>` puts one back for reassignment.
+- Moving a slice? `murmur reassign <bead> <old> <new> --note "..."
+  --carry merge` keeps the old worker's commits with it.
 
 ## Run the wave
 
 - `murmur status` - the wave on one screen: live agents, spool, ready
-  frontier. `murmur who` for just the agents.
+  frontier. `murmur who` for just the agents. A human watching along
+  wants `murmur status --watch`; you don't - it never returns.
+- Reading a verb's result in a script? Add `--json`: one document on
+  stdout with stable field names, commentary on stderr.
+- `murmur diff` shows every worker's tree without visiting its pane:
+  commits ahead/behind your branch, uncommitted changes, files touched.
+- Need an answer, not just a nudge? `murmur ask <worker> "status?"`
+  blocks until they `murmur reply` and prints the answer.
 - A silent worker gets `murmur tell <worker> "status?"` - it revives a
   finished pane and spools if they're away; nothing is silently lost.
+  `--urgent` puts a course correction ahead of older mail; `--ttl 10m`
+  lets a status ping expire instead of arriving stale.
+- `murmur stalls` lists slices held with no bead update, commit, or
+  activity for an hour (and the pane not working); `--nudge` pokes each
+  worker. The idle-wake plugin does it for you once per bead.
+- `murmur who` calls out tells left unacked for 5 minutes; when it
+  matters that one lands, `murmur tell <worker> "..." --wait-ack 10m`
+  blocks until they ack it.
+- Say it once to everyone: `murmur tell @workers "main moved, merge it"`
+  (`@all`, `@kind:<kind>`, `--except <name>`); the report shows who got it
+  live and who it waits for.
 - A pane stuck on a login or trust dialog ate its brief: clear the
   dialog, then `murmur tell <worker> --brief` re-delivers it.
 
@@ -64,7 +88,27 @@ Workers own exactly the slice you hand them. Do not wait for the human.
   time, gates each merge, warns on hub files, holds branches whose PR
   checks fail, and stops with the facts on a conflict. Merges, never
   rebases - worker branches are live checkouts.
-- `murmur pr status` snapshots every herd branch's PR and checks.
+- Branches merge in bead-dependency order, open beads last;
+  `murmur restack --plan` shows the order first, `--only-closed` leaves
+  unfinished slices out.
+- `murmur restack --forecast` shows which branches would conflict, and
+  which hubs two branches edit, before anything merges — re-slice or
+  sequence the work while it is cheap.
+- `--on-fail hold` keeps a red gate from stopping the line: the bad merge
+  is reset away, its owner hears the gate output, the rest still land.
+- Many small slices and a slow gate: `--batch 4` merges four clean
+  branches at once and gates once, bisecting a red batch.
+- A failed gate names its log under `.murmur/gates/`; hand it to the
+  owner with `murmur tell <worker> "..." --gate-log`.
+- A stop keeps its place: resolve the merge by hand, then
+  `murmur restack --continue`; `--skip <branch>` leaves one out and
+  `--abort` puts your branch back where the restack began.
+- `murmur pr open --all` pushes every worker branch and opens its PR from
+  the bead (idempotent); `murmur pr status` snapshots every herd branch's
+  PR and checks. GitHub, GitLab and Gitea all work; if the origin URL
+  doesn't say which, `git config murmur.host gh|glab|tea`.
+- `murmur pr feedback` tells each worker the new review comments and
+  failed checks on its PR; the idle-wake does it as panes settle.
 
 ## Tear down
 
@@ -98,6 +142,11 @@ The lead owns the wave; you own exactly one slice at a time.
    started it with --with), exercise your change against it - the repo's
    own docs say how. Your MURMUR_WORKTREE_SLOT env distinguishes your
    instance from your herdmates'.
+   Fallen behind the lead's branch? `murmur sync` merges it into yours;
+   resolve any conflict in your worktree (the lead hears when it hits a
+   hub file).
+   A `[pr feedback]` prompt is review on your PR: address it on your
+   branch and push.
 4. Close your slice: `murmur done <bead> --note "what changed"` - it
    closes the bead with attribution and the lead hears it. Can't finish?
    `murmur drop <bead>` hands it back with a word to the lead.
@@ -106,6 +155,10 @@ The lead owns the wave; you own exactly one slice at a time.
 
 - Ask instead of guessing: `murmur tell lead "..."` - delivered into
   their pane now, or spooled for their next idle.
+- A tell that asks for it (`read it? murmur ack <id>`): once you have
+  read it, `murmur ack <id>` - the lead sees who hasn't.
+- A `[question]` prompt is someone blocked on you: answer it with
+  `murmur reply <id> "..."` (the id is in the prompt), not a tell.
 - Hub files named in your brief are shared surface: keep edits minimal
   and tell the lead before touching them.
 - Log discovered work in beads (`bd create`, `bd dep add`) - never close
@@ -0,0 +1,250 @@
+//! `murmur stalls` — assignments nobody is moving.
+//!
+//! A bead can sit `in_progress` for hours while its worker's pane idles
+//! and its branch stays still; nothing else notices. A stall is an
+//! assignment where every signal is older than the threshold: the bead's
+//! own update time in beads, the last commit on `herd/<slug>/<name>`, and
+//! the agent's last journal entry or spooled tell — with the pane not
+//! working in herdr. The same check runs from the idle-wake plugin, once
+//! per bead, so a settling pane that is sitting on a stale slice hears it.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{fmt_span, record, tell_or_spool};
+use crate::fail;
+use crate::store::{self, Event, HerdSnap, Store, Verb};
+
+/// Quiet this long and an assignment counts as stalled.
+pub const STALL_AFTER_SECS: u64 = 3600;
+
+/// The idle-wake looks for stalls at most this often per agent.
+const RECHECK_SECS: u64 = 300;
+
+#[derive(Serialize)]
+pub struct Stall {
+    pub bead: String,
+    pub title: String,
+    pub agent: String,
+    /// herdr's agent_status, or "gone" when herdr doesn't know the name.
+    pub pane: String,
+    /// Seconds since beads last saw the bead change.
+    pub bead_quiet_secs: Option<u64>,
+    pub last_commit_secs: Option<u64>,
+    pub last_activity_secs: Option<u64>,
+}
+
+/// `murmur stalls` — list stalled assignments with the evidence; with
+/// `nudge`, tell each worker and send the lead one summary.
+pub fn run(
+    wave: Option<String>,
+    after: Option<String>,
+    nudge: bool,
+    json: bool,
+    from: Option<String>,
+) -> Result<()> {
+    if !crate::beads::available() {
+        fail!(
+            Missing,
+            "stalls needs beads (bd) — assignments live on the bead"
+        );
+    }
+    let store = Store::locate()?;
+    let after_secs = match after {
+        Some(a) => store::parse_duration(&a)?,
+        None => STALL_AFTER_SECS,
+    };
+    let snap = store.herd_select(wave.as_deref())?;
+    let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let stalls = find(&store, snap.as_ref(), &cwd, after_secs, None)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stalls)?);
+    } else if stalls.is_empty() {
+        println!(
+            "no stalled assignments (nothing in progress quiet past {})",
+            fmt_span(after_secs)
+        );
+    } else {
+        println!(
+            "{:<14} {:<12} {:<8} {:<11} {:<12} LAST ACTIVITY",
+            "BEAD", "AGENT", "PANE", "BEAD QUIET", "LAST COMMIT"
+        );
+        for s in &stalls {
+            println!(
+                "{:<14} {:<12} {:<8} {:<11} {:<12} {}",
+                s.bead,
+                s.agent,
+                s.pane,
+                ago(s.bead_quiet_secs),
+                ago(s.last_commit_secs),
+                ago(s.last_activity_secs)
+            );
+        }
+    }
+    if nudge && !stalls.is_empty() {
+        let me = crate::commands::sender(from);
+        for s in &stalls {
+            let delivery = tell_or_spool(&store, &me, &s.agent, &nudge_text(s, after_secs))?;
+            let mut event = Event::new(Verb::Tell, &me, &s.agent, delivery.outcome());
+            event.bead = s.bead.clone();
+            event.note = "stall nudge".into();
+            record(&store, event);
+        }
+        let lead = snap.as_ref().and_then(|w| w.agents.first().cloned());
+        if let Some(lead) = lead.filter(|l| *l != me) {
+            let all: Vec<&Stall> = stalls.iter().collect();
+            let _ = tell_or_spool(&store, &me, &lead, &summary(&all, after_secs));
+        }
+        println!("nudged {} worker(s)", stalls.len());
+    }
+    Ok(())
+}
+
+/// Every in-progress assignment (or just `only`'s) with no sign of life
+/// in `after_secs`. With a wave, only its agents; branch commits need its
+/// slug.
+pub fn find(
+    store: &Store,
+    snap: Option<&HerdSnap>,
+    cwd: &Path,
+    after_secs: u64,
+    only: Option<&str>,
+) -> Result<Vec<Stall>> {
+    let now = store::now_secs();
+    let fresh = |secs: Option<u64>| secs.is_some_and(|s| s < after_secs);
+    let live = crate::herdr::agents_info().unwrap_or_default();
+    let journal = store.journal_read();
+    let repo = snap
+        .map(|w| PathBuf::from(&w.repo))
+        .filter(|r| r.is_dir())
+        .unwrap_or_else(|| cwd.to_path_buf());
+    let mut out = Vec::new();
+    for issue in crate::beads::in_progress_in(Some(cwd))? {
+        let agent = issue.assignee.clone();
+        if agent.is_empty()
+            || only.is_some_and(|o| o != agent)
+            || snap.is_some_and(|w| !w.agents.contains(&agent))
+        {
+            continue;
+        }
+        let pane = live
+            .iter()
+            .find(|l| l.name == agent)
+            .map(|l| l.status.clone())
+            .unwrap_or_else(|| "gone".into());
+        if pane == "working" {
+            continue;
+        }
+        let bead_quiet_secs = (issue.updated > 0).then(|| now.saturating_sub(issue.updated));
+        let last_commit_secs = snap
+            .filter(|w| !w.slug.is_empty())
+            .and_then(|w| {
+                let branch = format!("herd/{}/{agent}", w.slug);
+                crate::restack::git_out(&repo, &["log", "-1", "--format=%ct", &branch]).ok()
+            })
+            .and_then(|t| t.trim().parse::<u64>().ok())
+            .map(|t| now.saturating_sub(t));
+        let last_activity_secs = journal
+            .iter()
+            .filter(|e| e.actor == agent)
+            .map(|e| e.ts)
+            .chain(store.spool_last_from(&agent))
+            .max()
+            .map(|ms| now.saturating_sub(ms / 1000));
+        if fresh(bead_quiet_secs) || fresh(last_commit_secs) || fresh(last_activity_secs) {
+            continue;
+        }
+        out.push(Stall {
+            bead: issue.id,
+            title: issue.title,
+            agent,
+            pane,
+            bead_quiet_secs,
+            last_commit_secs,
+            last_activity_secs,
+        });
+    }
+    Ok(out)
+}
+
+/// The idle-wake check: `name` just settled — if it is sitting on a
+/// stalled bead, nudge it and tell the lead, once per bead. Panes settle
+/// often, so it looks at most every [`RECHECK_SECS`]. Best-effort like
+/// the rest of the plugin.
+pub fn on_idle(store: &Store, name: &str, cwd: &Path, state_dir: &Path) {
+    if !crate::beads::available_in(cwd) {
+        return;
+    }
+    // the seen-file's mtime is the last time we looked
+    let path = state_dir.join(format!("stalled-{name}.json"));
+    let recent = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age.as_secs() < RECHECK_SECS);
+    if recent {
+        return;
+    }
+    let seen: HashSet<String> = std::fs::read(&path)
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default();
+    let snap = store.herd_of(name).ok().flatten();
+    let stalls = find(store, snap.as_ref(), cwd, STALL_AFTER_SECS, Some(name)).unwrap_or_default();
+    let new: Vec<&Stall> = stalls.iter().filter(|s| !seen.contains(&s.bead)).collect();
+    for s in &new {
+        let _ = tell_or_spool(store, "murmur", name, &nudge_text(s, STALL_AFTER_SECS));
+    }
+    if let Some(lead) = snap
+        .as_ref()
+        .and_then(|w| w.agents.first())
+        .filter(|l| *l != name && !new.is_empty())
+    {
+        let _ = tell_or_spool(store, "murmur", lead, &summary(&new, STALL_AFTER_SECS));
+    }
+    // written on every look, nothing new or not: it is the clock too
+    let all: Vec<&String> = seen.iter().chain(new.iter().map(|s| &s.bead)).collect();
+    if let Ok(bytes) = serde_json::to_vec(&all) {
+        let _ = std::fs::write(&path, bytes);
+    }
+}
+
+fn nudge_text(s: &Stall, after_secs: u64) -> String {
+    format!(
+        "[stalled] {bead} — {title}: no bead update, commit, or murmur activity in over {after} \
+         and your pane is {pane}. Still on it? Commit what you have and carry on; if something \
+         blocks you, `murmur tell lead \"...\"`; if you can't finish, `murmur drop {bead}`.",
+        bead = s.bead,
+        title = s.title,
+        after = fmt_span(after_secs),
+        pane = s.pane
+    )
+}
+
+fn summary(stalls: &[&Stall], after_secs: u64) -> String {
+    let lines: Vec<String> = stalls
+        .iter()
+        .map(|s| {
+            format!(
+                "{} ({}, pane {}, last commit {})",
+                s.bead,
+                s.agent,
+                s.pane,
+                ago(s.last_commit_secs)
+            )
+        })
+        .collect();
+    format!(
+        "[stalls] {} assignment(s) quiet past {}, worker(s) nudged: {}",
+        stalls.len(),
+        fmt_span(after_secs),
+        lines.join("; ")
+    )
+}
+
+pub fn ago(secs: Option<u64>) -> String {
+    secs.map(|s| format!("{} ago", fmt_span(s)))
+        .unwrap_or_else(|| "—".into())
+}
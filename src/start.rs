@@ -6,12 +6,16 @@
 //! `murmur assign`, completion with `murmur done`, both on the bead.
 
 use anyhow::{bail, Context, Result};
+use serde::Serialize;
 
 use crate::beads;
 use crate::cloud;
 use crate::commands;
+use crate::exit::{self, Kind};
+use crate::fail;
 use crate::herdr;
-use crate::store::{HerdSnap, Store};
+use crate::say;
+use crate::store::{Event, HerdSnap, Outcome, Store, Verb};
 
 pub struct Opts {
     pub goal: Option<String>,
@@ -36,6 +40,41 @@ pub struct Opts {
     /// Plan-first: start only the lead, briefed to slice the goal into
     /// beads and summon its own workers when the plan is ready.
     pub plan: bool,
+    /// Print a `StartResult` on stdout; the running commentary goes to
+    /// stderr.
+    pub json: bool,
+}
+
+/// `murmur start --json`: what came up, and where to find it.
+#[derive(Serialize, Default)]
+pub struct StartResult {
+    /// The wave's notebook key (`--wave`); empty for an all-cloud herd,
+    /// which keeps no snapshot.
+    pub wave: String,
+    /// The goal bead, or the label slug when there is no beads.
+    pub goal: String,
+    pub title: String,
+    pub workspace: String,
+    /// The notebook this wave lives in.
+    pub store: String,
+    /// Lead first.
+    pub agents: Vec<StartedAgent>,
+    pub worktrees: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct StartedAgent {
+    pub name: String,
+    pub kind: String,
+    /// herdr pane id, or `cloud:<launch id>`.
+    pub pane: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+    /// The stored brief (`murmur tell <name> --brief` re-delivers it).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brief: Option<String>,
 }
 
 /// What the wave is about: a bead when beads is here, a bare label when
@@ -49,6 +88,7 @@ pub struct Goal {
 }
 
 pub fn run(opts: Opts) -> Result<()> {
+    crate::report::set_json(opts.json);
     let workers = if opts.plan { 1 } else { opts.workers.max(1) };
     let (bead_id, goal) = split_goal(opts.goal, opts.bead, beads::available())?;
 
@@ -67,7 +107,7 @@ pub fn run(opts: Opts) -> Result<()> {
     };
     store.init()?;
     if opts.board.is_some() {
-        println!(
+        say!(
             "board  scoped to {} — reach it with MURMUR_DIR={} (panes get it automatically)",
             store.root().display(),
             store.root().display()
@@ -75,7 +115,7 @@ pub fn run(opts: Opts) -> Result<()> {
     }
 
     let goal = resolve_goal(bead_id.as_deref(), goal)?;
-    println!("work   {}", goal.title);
+    say!("work   {}", goal.title);
 
     // Cloud kinds (cloud:<backend>) are parsed before any herdr decision:
     // an all-cloud herd needs no panes at all, and a mixed herd must fail
@@ -105,7 +145,7 @@ pub fn run(opts: Opts) -> Result<()> {
     let n_cloud = kinds.iter().filter(|k| cloud::is_cloud(k)).count();
     if caller.is_none() {
         if n_cloud > 0 && n_cloud == kinds.len() {
-            return start_cloud_only(&goal, &kinds);
+            return start_cloud_only(&store, &goal, &kinds, opts.json);
         }
         if n_cloud > 0 && cloud::is_cloud(&kinds[0]) {
             bail!(
@@ -118,7 +158,8 @@ pub fn run(opts: Opts) -> Result<()> {
     // Murmur is tied to herdr: local agents need panes, delivery, and
     // presence, and herdr owns all three.
     if !herdr::available() {
-        bail!(
+        fail!(
+            Missing,
             "murmur needs a running herdr for local agents — start herdr first, \
              or go all-cloud (--kind cloud:cursor=2)"
         );
@@ -155,12 +196,17 @@ pub fn run(opts: Opts) -> Result<()> {
     } else {
         None
     };
+    let base = repo
+        .as_ref()
+        .and_then(|r| crate::restack::git_out(r, &["rev-parse", "HEAD"]).ok())
+        .unwrap_or_default();
     let shared_store = store
         .root()
         .canonicalize()
         .unwrap_or_else(|_| store.root().to_path_buf());
     let mut used = herdr::live_names();
     let mut herd: Vec<(String, String, String)> = Vec::new(); // (name, kind, pane)
+    let mut started: Vec<StartedAgent> = Vec::new();
     let mut last_pane: Option<String> = None; // last *local* pane, for splits
     let mut cloud_repo: Option<cloud::RepoRef> = None;
     let mut workspace_id = String::new();
@@ -172,8 +218,16 @@ pub fn run(opts: Opts) -> Result<()> {
         // as the split anchor and move on to spawning its workers.
         if caller_leads && i == 0 {
             let pane = std::env::var("HERDR_PANE_ID").unwrap_or_else(|_| "current".into());
-            println!("lead   {base}  (you — this pane)");
+            say!("lead   {base}  (you — this pane)");
             herd.push((base.clone(), kind.clone(), pane.clone()));
+            started.push(StartedAgent {
+                name: base.clone(),
+                kind: kind.clone(),
+                pane: pane.clone(),
+                branch: None,
+                worktree: None,
+                brief: None,
+            });
             last_pane = Some(pane);
             continue;
         }
@@ -194,7 +248,7 @@ pub fn run(opts: Opts) -> Result<()> {
             match cloud::launch(kind, &brief, cloud_repo.as_ref().unwrap()) {
                 Ok(l) => {
                     crate::fleet::record_start(kind);
-                    println!("cloud  {base}  {}  ({kind})", l.id);
+                    say!("cloud  {base}  {}  ({kind})", l.id);
                     let note = format!(
                         "[cloud] {base} launched on {} (id {id}). It can't hear murmur — \
                          follow up with `murmur cloud prompt {id} \"...\"`, check \
@@ -206,6 +260,14 @@ pub fn run(opts: Opts) -> Result<()> {
                     );
                     let _ = commands::tell_or_spool(&store, base, &roles[0].0, &note);
                     herd.push((base.clone(), kind.clone(), format!("cloud:{}", l.id)));
+                    started.push(StartedAgent {
+                        name: base.clone(),
+                        kind: kind.clone(),
+                        pane: format!("cloud:{}", l.id),
+                        branch: None,
+                        worktree: None,
+                        brief: None,
+                    });
                 }
                 Err(e) => eprintln!("murmur: could not launch {kind} as {base}: {e}"),
             }
@@ -220,7 +282,7 @@ pub fn run(opts: Opts) -> Result<()> {
             Some(repo) => {
                 match add_worktree(repo, &herd_slug, &name, slot, opts.worktree_cmd.as_deref()) {
                     Ok((dir, branch)) => {
-                        println!("tree   {name}  {}  ({branch})", dir.display());
+                        say!("tree   {name}  {}  ({branch})", dir.display());
                         worktrees.push(dir.display().to_string());
                         (dir, Some(branch))
                     }
@@ -240,9 +302,9 @@ pub fn run(opts: Opts) -> Result<()> {
             let (ws, root) = herdr::create_workspace(&label, &cwd)?;
             workspace_id = ws.clone();
             if !ws.is_empty() {
-                println!("space  {label}  {ws}  root {root}");
+                say!("space  {label}  {ws}  root {root}");
             } else {
-                println!("space  {label}  root {root}");
+                say!("space  {label}  root {root}");
             }
             last_pane = Some(root);
         }
@@ -255,7 +317,7 @@ pub fn run(opts: Opts) -> Result<()> {
             &slot_env,
         )?;
         last_pane = Some(pane.clone());
-        println!("pane   {name}  {pane}  ({kind})");
+        say!("pane   {name}  {pane}  ({kind})");
         let _ = herdr::wait_shell(&pane);
         if let Err(e) = herdr::start_agent(&name, kind, &pane) {
             eprintln!("murmur: could not start {kind} as {name}: {e}");
@@ -283,7 +345,7 @@ pub fn run(opts: Opts) -> Result<()> {
                 opts.with.as_deref(),
             )
         };
-        let _ = store.brief_save(&name, &brief);
+        let brief_path = store.brief_save(&name, &brief).ok();
         if let Err(e) = herdr::prompt(&name, &brief) {
             eprintln!(
                 "murmur: could not prompt {name}: {e} — re-deliver with \
@@ -305,7 +367,7 @@ pub fn run(opts: Opts) -> Result<()> {
                 Ok(svc) => {
                     let _ = herdr::wait_shell(&svc);
                     match herdr::run_in_pane(&svc, cmd) {
-                        Ok(()) => println!("serve  {name}  {svc}  ({cmd})"),
+                        Ok(()) => say!("serve  {name}  {svc}  ({cmd})"),
                         Err(e) => eprintln!(
                             "murmur: service pane {svc} for {name}: could not run '{cmd}': {e}"
                         ),
@@ -314,6 +376,14 @@ pub fn run(opts: Opts) -> Result<()> {
                 Err(e) => eprintln!("murmur: no service pane for {name}: {e}"),
             }
         }
+        started.push(StartedAgent {
+            name: name.clone(),
+            kind: kind.clone(),
+            pane: pane.clone(),
+            worktree: branch.is_some().then(|| pane_cwd.display().to_string()),
+            branch,
+            brief: brief_path.map(|p| p.display().to_string()),
+        });
         herd.push((name, kind.clone(), pane));
     }
 
@@ -321,35 +391,63 @@ pub fn run(opts: Opts) -> Result<()> {
         bail!("herdr is up but no agent started — check `herdr agent start --help`");
     }
 
-    store.herd_save(&HerdSnap {
+    let snap = HerdSnap {
         workspace_id: workspace_id.clone(),
         label: label.clone(),
+        bead: if goal.external {
+            goal.id.clone()
+        } else {
+            String::new()
+        },
+        started: crate::store::now_secs(),
         agents: herd.iter().map(|(n, _, _)| n.clone()).collect(),
+        kinds: herd.iter().map(|(_, k, _)| k.clone()).collect(),
         repo: repo
             .as_ref()
             .map(|p| p.display().to_string())
             .unwrap_or_default(),
         worktrees,
         slug: herd_slug,
+        base,
         hubs: opts.hubs.clone(),
-    })?;
+    };
+    store.herd_save(&snap)?;
+    let actor = commands::sender(None);
+    for (name, kind, _) in &herd {
+        let mut event = Event::new(Verb::Start, &actor, name, Outcome::Started);
+        event.bead = snap.bead.clone();
+        event.wave = snap.key();
+        event.note = kind.clone();
+        commands::record(&store, event);
+    }
 
-    println!(
+    say!(
         "\nherd   {}",
         herd.iter()
             .map(|(n, k, _)| format!("{n} ({k})"))
             .collect::<Vec<_>>()
             .join(", ")
     );
-    println!("watch  murmur status");
-    println!("stop   murmur stop");
+    say!("watch  murmur status");
+    say!("stop   murmur stop");
     if caller_leads {
-        println!(
+        say!(
             "\nYou lead from this pane. Assign each slice (`murmur assign <bead> <worker>`), \
              nudge stalled workers (`murmur tell <worker> \"status?\"`), and run the merge \
              queue (`murmur restack`, `murmur pr status`)."
         );
     }
+    if opts.json {
+        crate::report::emit(&StartResult {
+            wave: snap.key(),
+            goal: goal.id.clone(),
+            title: goal.title.clone(),
+            workspace: snap.workspace_id.clone(),
+            store: store.root().display().to_string(),
+            agents: started,
+            worktrees: snap.worktrees.clone(),
+        })?;
+    }
     Ok(())
 }
 
@@ -366,7 +464,7 @@ fn resolve_goal(bead_id: Option<&str>, goal: Option<String>) -> Result<Goal> {
             .map(|i| format!("{} ({})", i.id, i.title))
             .collect();
         if !frontier.is_empty() {
-            println!("ready  {}", frontier.join(", "));
+            say!("ready  {}", frontier.join(", "));
         }
         return Ok(Goal {
             id: issue.id,
@@ -379,7 +477,7 @@ fn resolve_goal(bead_id: Option<&str>, goal: Option<String>) -> Result<Goal> {
     if beads::available() {
         match beads::create(&title, "") {
             Ok(issue) => {
-                println!("bead   {}  {}", issue.id, issue.title);
+                say!("bead   {}  {}", issue.id, issue.title);
                 return Ok(Goal {
                     id: issue.id,
                     title: issue.title,
@@ -414,11 +512,13 @@ fn caller_agent() -> Option<String> {
     herdr::started_agent_name()
 }
 
-/// Tear down the last `murmur start` herd: close its Herdr workspace and
-/// remove the worktrees start created. Run this from a pane that is *not*
-/// inside that workspace. `--board` targets a named board's notebook the
-/// same way `start --board` created it.
-pub fn stop(board: Option<String>) -> Result<()> {
+/// Tear down one `murmur start` herd — `wave` picks it, else the only or
+/// most recent: close its Herdr workspace and remove the worktrees start
+/// created. Run this from a pane that is *not* inside that workspace.
+/// `--board` targets a named board's notebook the same way `start --board`
+/// created it.
+pub fn stop(board: Option<String>, wave: Option<String>, json: bool) -> Result<()> {
+    crate::report::set_json(json);
     let store = match &board {
         Some(name) => Store::at(
             std::env::current_dir()
@@ -427,22 +527,34 @@ pub fn stop(board: Option<String>) -> Result<()> {
         ),
         None => Store::locate()?,
     };
-    let snap = store
-        .herd_load()?
-        .context("no running herd (.murmur/herd.json missing) — start one first")?;
+    let snap = store.herd_select(wave.as_deref())?.ok_or_else(|| {
+        exit::err(
+            Kind::NotFound,
+            "no running herd in the notebook — start one first",
+        )
+    })?;
 
     if let Ok(here) = std::env::var("HERDR_WORKSPACE_ID") {
         if !snap.workspace_id.is_empty() && here == snap.workspace_id {
-            bail!(
+            fail!(
+                Refused,
                 "won't close workspace {} from inside it — run murmur stop from another workspace",
                 snap.workspace_id
             );
         }
     }
 
+    let mut report = StopReport {
+        wave: snap.key(),
+        workspace: None,
+        worktrees: Vec::new(),
+    };
     if !snap.workspace_id.is_empty() && herdr::available() {
         match herdr::close_workspace(&snap.workspace_id) {
-            Ok(()) => println!("closed workspace {}", snap.workspace_id),
+            Ok(()) => {
+                say!("closed workspace {}", snap.workspace_id);
+                report.workspace = Some(snap.workspace_id.clone());
+            }
             Err(e) => eprintln!(
                 "murmur: could not close workspace {}: {e}",
                 snap.workspace_id
@@ -459,7 +571,10 @@ pub fn stop(board: Option<String>) -> Result<()> {
             .current_dir(&snap.repo)
             .output();
         match out {
-            Ok(o) if o.status.success() => println!("removed worktree {path}"),
+            Ok(o) if o.status.success() => {
+                say!("removed worktree {path}");
+                report.worktrees.push(path.clone());
+            }
             Ok(o) => eprintln!(
                 "murmur: git worktree remove {path}: {}",
                 String::from_utf8_lossy(&o.stderr).trim()
@@ -468,8 +583,17 @@ pub fn stop(board: Option<String>) -> Result<()> {
         }
     }
 
-    store.herd_clear()?;
-    println!(
+    store.herd_remove(&snap.key())?;
+    let mut event = Event::new(
+        Verb::Stop,
+        &commands::sender(None),
+        &snap.key(),
+        Outcome::Stopped,
+    );
+    event.bead = snap.bead.clone();
+    event.wave = snap.key();
+    commands::record(&store, event);
+    say!(
         "stopped herd {}",
         if snap.label.is_empty() {
             "(unnamed)"
@@ -477,9 +601,22 @@ pub fn stop(board: Option<String>) -> Result<()> {
             snap.label.as_str()
         }
     );
+    if json {
+        crate::report::emit(&report)?;
+    }
     Ok(())
 }
 
+/// `murmur stop --json`: what was torn down.
+#[derive(Serialize)]
+pub struct StopReport {
+    pub wave: String,
+    /// The Herdr workspace closed; null when none was (or could be).
+    pub workspace: Option<String>,
+    /// Worktrees removed.
+    pub worktrees: Vec<String>,
+}
+
 /// An explicit `--bead` always means beads (and fails loudly without `bd`);
 /// a bare id is only *detected* when beads can actually serve it, so on a
 /// machine with no beads, "phase-2" is a goal, not a lookup that errors.
@@ -906,31 +1043,55 @@ fn body_block(goal: &Goal) -> String {
 
 /// All-cloud herd: no panes, no lead — the human is the integration point.
 /// Launch each worker with a git-facing brief and print how to follow up.
-fn start_cloud_only(goal: &Goal, kinds: &[String]) -> Result<()> {
+fn start_cloud_only(store: &Store, goal: &Goal, kinds: &[String], json: bool) -> Result<()> {
     let cwd = std::env::current_dir().context("cannot determine cwd")?;
     let repo = cloud::repo_ref(&cwd)?;
     let names: Vec<String> = (1..=kinds.len()).map(|i| format!("w{i}")).collect();
     let roles: Vec<(String, String)> = names.into_iter().zip(kinds.iter().cloned()).collect();
-    let mut launched = 0;
+    let mut result = StartResult {
+        goal: goal.id.clone(),
+        title: goal.title.clone(),
+        store: store.root().display().to_string(),
+        ..StartResult::default()
+    };
     for (name, kind) in &roles {
         let brief = cloud_brief(name, kind, &roles, goal);
         match cloud::launch(kind, &brief, &repo) {
             Ok(l) => {
                 crate::fleet::record_start(kind);
-                println!("cloud  {name}  {}  ({kind})", l.id);
-                launched += 1;
+                say!("cloud  {name}  {}  ({kind})", l.id);
+                let mut event =
+                    Event::new(Verb::Start, &commands::sender(None), name, Outcome::Started);
+                event.bead = if goal.external {
+                    goal.id.clone()
+                } else {
+                    String::new()
+                };
+                event.note = format!("{kind} {}", l.id);
+                commands::record(store, event);
+                result.agents.push(StartedAgent {
+                    name: name.clone(),
+                    kind: kind.clone(),
+                    pane: format!("cloud:{}", l.id),
+                    branch: None,
+                    worktree: None,
+                    brief: None,
+                });
             }
             Err(e) => eprintln!("murmur: could not launch {kind} as {name}: {e}"),
         }
     }
-    if launched == 0 {
+    if result.agents.is_empty() {
         bail!("no cloud agent launched");
     }
-    println!(
+    say!(
         "\nno local lead — you are the integration point: review the PRs referencing {}.",
         goal.id
     );
-    println!("watch  murmur cloud status <id>   ·   nudge: murmur cloud prompt <id> \"...\"   ·   find: murmur cloud list");
+    say!("watch  murmur cloud status <id>   ·   nudge: murmur cloud prompt <id> \"...\"   ·   find: murmur cloud list");
+    if json {
+        crate::report::emit(&result)?;
+    }
     Ok(())
 }
 
@@ -3,17 +3,26 @@
 //! ```text
 //! .murmur/
 //!   .gitignore            self-ignoring, like target/
-//!   herd.json             the running wave: workspace, agents, worktrees, hubs
+//!   herds/<wave>.json     one per running wave: workspace, agents, worktrees, hubs
 //!   briefs/<name>.txt     each agent's brief, kept for re-delivery
 //!   spool/<name>/*.json   undelivered tells, drained into prompts on idle-wake
+//!   acks/<name>/<id>.json tells that agent has not acknowledged yet
+//!   acks/<name>/<id>.expired  ones that expired in the spool unread
+//!   asks/<id>.json        open questions from `murmur ask`
+//!   asks/<id>.answer      how each ended: the reply, or the asker giving up
+//!   restack/<wave>.json   the merge queue: each branch's state, the pre-restack HEAD
+//!   gates/<branch>-<ts>.log  each restack gate run: output, exit code, duration
+//!   journal.jsonl         append-only record of what every verb did
 //!   tmp/                  staging for atomic renames
 //! ```
 //!
 //! Everything live belongs to herdr (panes, presence, delivery); everything
 //! durable about the *work* belongs to beads (plan, assignment, notes).
 //! This directory holds only what neither owns: the wave snapshot, the
-//! briefs, and messages waiting for an agent that wasn't listening.
+//! briefs, messages waiting for an agent that wasn't listening, and the
+//! journal of what murmur itself did.
 
+use crate::fail;
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -30,30 +39,348 @@ pub struct Spooled {
     /// unix millis
     pub ts: u64,
     pub body: String,
+    /// Delivered ahead of everything else waiting.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub urgent: bool,
+    /// unix millis after which the tell is stale: dropped, never delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<u64>,
+    /// The message id the agent acks with (`murmur ack <id>`).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
 }
 
-/// Snapshot of the last `murmur start` herd, so `murmur stop` can tear it
-/// down without the human remembering pane ids.
+impl Spooled {
+    pub fn new(from: &str, to: &str, body: &str) -> Spooled {
+        Spooled {
+            from: from.to_string(),
+            to: to.to_string(),
+            ts: now_millis(),
+            body: body.to_string(),
+            urgent: false,
+            expires: None,
+            id: String::new(),
+        }
+    }
+
+    pub fn expired(&self, now: u64) -> bool {
+        self.expires.is_some_and(|t| now >= t)
+    }
+}
+
+/// A tell the recipient has not acknowledged. "Delivered" only means the
+/// prompt landed; the ack is the model saying it read it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unacked {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+    /// unix millis
+    pub ts: u64,
+}
+
+/// An open `murmur ask`, written once by the asker. Nobody rewrites it:
+/// the outcome is a separate `Settled` file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Question {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+    /// unix millis
+    pub ts: u64,
+    pub question: String,
+    /// The blocked `murmur ask` process, so a reply can tell a killed
+    /// asker from a waiting one.
+    #[serde(default)]
+    pub pid: u32,
+}
+
+/// How a question ended, created exactly once by whoever gets there
+/// first: the reply (`answer` set) or the asker giving up (`answer` None).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settled {
+    pub by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+}
+
+/// Where one branch stands in a restack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BranchState {
+    Pending,
+    Merged,
+    Held,
+    Conflict,
+    /// Merged, but the gate failed on it.
+    Failed,
+    Skipped,
+}
+
+impl BranchState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BranchState::Pending => "pending",
+            BranchState::Merged => "merged",
+            BranchState::Held => "held",
+            BranchState::Conflict => "conflict",
+            BranchState::Failed => "failed",
+            BranchState::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueItem {
+    pub branch: String,
+    pub state: BranchState,
+    /// HEAD after the merge, once merged.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub commit: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+/// A restack in flight, modeled on git's sequencer: the queue survives a
+/// stop so `--continue`, `--skip`, and `--abort` pick up where it left off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestackQueue {
+    pub wave: String,
+    /// The integration branch the queue merges into.
+    pub into: String,
+    /// HEAD before the first merge — what `--abort` restores.
+    pub head: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cmd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_fail: Option<OnFail>,
+    /// Branches merged (and gated) together; one at a time when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<usize>,
+    pub items: Vec<QueueItem>,
+}
+
+/// What restack does with a merge that fails its gate, instead of
+/// stopping: either way the branch is held and its owner told.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OnFail {
+    /// Undo it with a revert commit — the failed attempt stays in history.
+    Revert,
+    /// Reset it away, as if it was never tried.
+    Hold,
+}
+
+impl RestackQueue {
+    /// Stopped on a branch, or branches still to go.
+    pub fn in_progress(&self) -> bool {
+        self.items.iter().any(|i| {
+            matches!(
+                i.state,
+                BranchState::Pending | BranchState::Conflict | BranchState::Failed
+            )
+        })
+    }
+}
+
+/// Snapshot of one `murmur start` herd, so `murmur stop` can tear it
+/// down without the human remembering pane ids. A notebook holds one per
+/// live wave, keyed by `key()`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HerdSnap {
     #[serde(default)]
     pub workspace_id: String,
     #[serde(default)]
     pub label: String,
+    /// The goal bead, when the wave has one.
+    #[serde(default)]
+    pub bead: String,
+    /// unix secs; the most recent wave is the default selection
+    #[serde(default)]
+    pub started: u64,
     #[serde(default)]
     pub agents: Vec<String>,
+    /// Each agent's kind, in `agents` order.
+    #[serde(default)]
+    pub kinds: Vec<String>,
     #[serde(default)]
     pub repo: String,
     #[serde(default)]
     pub worktrees: Vec<String>,
     #[serde(default)]
     pub slug: String,
+    /// The commit every herd branch forked from (worktree waves).
+    #[serde(default)]
+    pub base: String,
     /// Paths the whole herd converges on (shared registries, barrel files):
     /// named in every brief, checked by `murmur restack`.
     #[serde(default)]
     pub hubs: Vec<String>,
 }
 
+impl HerdSnap {
+    /// The wave's notebook key: its slug, else a filesystem-safe label.
+    pub fn key(&self) -> String {
+        if !self.slug.is_empty() {
+            return self.slug.clone();
+        }
+        let label: String = self
+            .label
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let label = label.trim_matches(|c| c == '-' || c == '.');
+        if label.is_empty() {
+            "herd".into()
+        } else {
+            label.chars().take(64).collect()
+        }
+    }
+
+    /// The kind `agent` was started as, when the snapshot recorded it.
+    pub fn kind_of(&self, agent: &str) -> Option<&str> {
+        let i = self.agents.iter().position(|a| a == agent)?;
+        self.kinds.get(i).map(|k| k.as_str())
+    }
+
+    /// Does `sel` name this wave? Slug, label, goal bead, or workspace id.
+    pub fn matches(&self, sel: &str) -> bool {
+        [
+            &self.key(),
+            &self.slug,
+            &self.label,
+            &self.bead,
+            &self.workspace_id,
+        ]
+        .iter()
+        .any(|s| !s.is_empty() && s.as_str() == sel)
+    }
+}
+
+/// The verb that produced a journal event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verb {
+    Start,
+    Stop,
+    Tell,
+    Assign,
+    Done,
+    Drop,
+    Restack,
+    Ack,
+    Ask,
+    Reply,
+    Reassign,
+    Sync,
+    Pr,
+}
+
+impl Verb {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verb::Start => "start",
+            Verb::Stop => "stop",
+            Verb::Tell => "tell",
+            Verb::Assign => "assign",
+            Verb::Done => "done",
+            Verb::Drop => "drop",
+            Verb::Restack => "restack",
+            Verb::Ack => "ack",
+            Verb::Ask => "ask",
+            Verb::Reply => "reply",
+            Verb::Reassign => "reassign",
+            Verb::Sync => "sync",
+            Verb::Pr => "pr",
+        }
+    }
+}
+
+/// How it went: a delivery, a bead transition, or a merge-queue result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Delivered,
+    Spooled,
+    Closed,
+    Reopened,
+    Started,
+    Stopped,
+    Merged,
+    Held,
+    Conflict,
+    Skipped,
+    Failed,
+    Expired,
+    Acked,
+    Answered,
+    Aborted,
+    Opened,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Delivered => "delivered",
+            Outcome::Spooled => "spooled",
+            Outcome::Closed => "closed",
+            Outcome::Reopened => "reopened",
+            Outcome::Started => "started",
+            Outcome::Stopped => "stopped",
+            Outcome::Merged => "merged",
+            Outcome::Held => "held",
+            Outcome::Conflict => "conflict",
+            Outcome::Skipped => "skipped",
+            Outcome::Failed => "failed",
+            Outcome::Expired => "expired",
+            Outcome::Acked => "acked",
+            Outcome::Answered => "answered",
+            Outcome::Aborted => "aborted",
+            Outcome::Opened => "opened",
+        }
+    }
+}
+
+/// One line of `journal.jsonl`: who did what to whom, and how it went.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    /// unix millis
+    pub ts: u64,
+    pub verb: Verb,
+    pub actor: String,
+    /// An agent name, a branch, or a wave — whatever the verb acted on.
+    pub target: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub bead: String,
+    /// The wave key, when one is known.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub wave: String,
+    pub outcome: Outcome,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl Event {
+    pub fn new(verb: Verb, actor: &str, target: &str, outcome: Outcome) -> Event {
+        Event {
+            ts: now_millis(),
+            verb,
+            actor: actor.to_string(),
+            target: target.to_string(),
+            bead: String::new(),
+            wave: String::new(),
+            outcome,
+            note: String::new(),
+        }
+    }
+}
+
 pub struct Store {
     root: PathBuf,
 }
@@ -116,26 +443,24 @@ impl Store {
 
     /// Queue a tell for an agent that isn't listening right now. The
     /// idle-wake plugin delivers it as a prompt when the pane settles.
-    pub fn spool_push(&self, from: &str, to: &str, body: &str) -> Result<()> {
+    /// The file name carries the tell's own timestamp, so a tell put back
+    /// after a failed delivery keeps its place in the queue.
+    pub fn spool_push(&self, msg: &Spooled) -> Result<()> {
+        let to = msg.to.as_str();
         valid_name(to)?;
         self.init()?;
         let dir = self.root.join("spool").join(to);
         fs::create_dir_all(&dir)?;
-        let ts = now_millis();
-        let msg = Spooled {
-            from: from.to_string(),
-            to: to.to_string(),
-            ts,
-            body: body.to_string(),
-        };
-        let id = next_id(ts);
+        let id = next_id(msg.ts);
         let tmp = self.root.join("tmp").join(format!("spool-{to}-{id}"));
-        fs::write(&tmp, serde_json::to_vec(&msg)?)?;
+        fs::write(&tmp, serde_json::to_vec(msg)?)?;
         fs::rename(&tmp, dir.join(format!("{id}.json")))?;
         Ok(())
     }
 
-    /// Take everything waiting for `name`, oldest first, removing it.
+    /// Take everything waiting for `name`, removing it: urgent tells
+    /// first, then oldest first. Expired tells come back too — the caller
+    /// drops them and says so.
     pub fn spool_drain(&self, name: &str) -> Result<Vec<Spooled>> {
         valid_name(name)?;
         let dir = self.root.join("spool").join(name);
@@ -155,6 +480,7 @@ impl Store {
             }
             let _ = fs::remove_file(&path);
         }
+        out.sort_by_key(|m| !m.urgent); // stable: oldest first within each
         Ok(out)
     }
 
@@ -179,16 +505,257 @@ impl Store {
         out
     }
 
+    // ---- restack queue ----
+
+    pub fn queue_save(&self, q: &RestackQueue) -> Result<()> {
+        valid_name(&q.wave)?;
+        self.init()?;
+        let dir = self.root.join("restack");
+        fs::create_dir_all(&dir)?;
+        let tmp =
+            self.root
+                .join("tmp")
+                .join(format!("restack-{}-{}", q.wave, next_id(now_millis())));
+        fs::write(&tmp, serde_json::to_vec_pretty(q)?)?;
+        fs::rename(&tmp, dir.join(format!("{}.json", q.wave)))?;
+        Ok(())
+    }
+
+    pub fn queue_load(&self, wave: &str) -> Result<Option<RestackQueue>> {
+        valid_name(wave)?;
+        let path = self.root.join("restack").join(format!("{wave}.json"));
+        match fs::read(&path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes).with_context(|| {
+                format!("{} is not a restack queue", path.display())
+            })?)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn queue_remove(&self, wave: &str) -> Result<()> {
+        valid_name(wave)?;
+        let _ = fs::remove_file(self.root.join("restack").join(format!("{wave}.json")));
+        Ok(())
+    }
+
+    // ---- gate logs ----
+
+    /// Keep one restack gate run: `gates/<branch>-<ts>.log`, the branch's
+    /// slashes flattened (`herd-s-w1-1760000000000.log`).
+    pub fn gate_log_save(&self, branch: &str, text: &str) -> Result<PathBuf> {
+        self.init()?;
+        let dir = self.root.join("gates");
+        fs::create_dir_all(&dir)?;
+        let flat = gate_log_prefix(branch);
+        valid_name(&flat)?;
+        let path = dir.join(format!("{flat}-{}.log", now_millis()));
+        fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    /// The newest gate log for `branch`, if restack ever gated it.
+    pub fn gate_log_latest(&self, branch: &str) -> Option<PathBuf> {
+        let prefix = format!("{}-", gate_log_prefix(branch));
+        fs::read_dir(self.root.join("gates"))
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().into_owned();
+                let ts = name
+                    .strip_prefix(&prefix)?
+                    .strip_suffix(".log")?
+                    .parse::<u64>()
+                    .ok()?;
+                Some((ts, e.path()))
+            })
+            .max_by_key(|(ts, _)| *ts)
+            .map(|(_, path)| path)
+    }
+
+    // ---- asks ----
+
+    pub fn question_save(&self, q: &Question) -> Result<()> {
+        valid_name(&q.id)?;
+        self.init()?;
+        let dir = self.root.join("asks");
+        fs::create_dir_all(&dir)?;
+        let tmp = self
+            .root
+            .join("tmp")
+            .join(format!("ask-{}-{}", q.id, next_id(now_millis())));
+        fs::write(&tmp, serde_json::to_vec(q)?)?;
+        fs::rename(&tmp, dir.join(format!("{}.json", q.id)))?;
+        Ok(())
+    }
+
+    pub fn question_load(&self, id: &str) -> Result<Option<Question>> {
+        valid_name(id)?;
+        let path = self.root.join("asks").join(format!("{id}.json"));
+        match fs::read(&path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes).with_context(|| {
+                format!("{} is not a murmur question", path.display())
+            })?)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn question_remove(&self, id: &str) -> Result<()> {
+        valid_name(id)?;
+        let asks = self.root.join("asks");
+        let _ = fs::remove_file(asks.join(format!("{id}.json")));
+        let _ = fs::remove_file(asks.join(format!("{id}.answer")));
+        Ok(())
+    }
+
+    /// Settle question `id` unless someone already did: the file is
+    /// created with `create_new`, so an answer is never overwritten.
+    /// False when it was settled first.
+    pub fn question_settle(&self, id: &str, how: &Settled) -> Result<bool> {
+        use std::io::Write;
+        valid_name(id)?;
+        let dir = self.root.join("asks");
+        fs::create_dir_all(&dir)?;
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(dir.join(format!("{id}.answer")))
+        {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Ok(false),
+            Err(e) => return Err(e.into()),
+        };
+        file.write_all(&serde_json::to_vec(how)?)?;
+        Ok(true)
+    }
+
+    /// How `id` ended, once whoever settled it has finished writing.
+    pub fn question_settled(&self, id: &str) -> Result<Option<Settled>> {
+        valid_name(id)?;
+        let path = self.root.join("asks").join(format!("{id}.answer"));
+        match fs::read(&path) {
+            // created but not written yet: not settled as far as we can see
+            Ok(bytes) if bytes.is_empty() => Ok(None),
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes).ok()),
+            Err(_) => Ok(None),
+        }
+    }
+
+    // ---- acks ----
+
+    /// Remember that `msg.to` owes an ack for `msg.id`.
+    pub fn ack_expect(&self, msg: &Unacked) -> Result<()> {
+        valid_name(&msg.to)?;
+        valid_name(&msg.id)?;
+        self.init()?;
+        let dir = self.root.join("acks").join(&msg.to);
+        fs::create_dir_all(&dir)?;
+        let tmp = self
+            .root
+            .join("tmp")
+            .join(format!("ack-{}-{}", msg.to, msg.id));
+        fs::write(&tmp, serde_json::to_vec(msg)?)?;
+        fs::rename(&tmp, dir.join(format!("{}.json", msg.id)))?;
+        Ok(())
+    }
+
+    /// Settle `id` for its recipient `by`; anyone else is refused. None
+    /// when nothing is owed under that id (already acked, expired, or
+    /// never sent).
+    pub fn ack(&self, id: &str, by: &str) -> Result<Option<Unacked>> {
+        valid_name(id)?;
+        for msg in self.unacked_all() {
+            if msg.id == id {
+                if msg.to != by {
+                    fail!(
+                        Refused,
+                        "{id} was told to {}, not {by} — only its recipient acks it",
+                        msg.to
+                    );
+                }
+                fs::remove_file(
+                    self.root
+                        .join("acks")
+                        .join(&msg.to)
+                        .join(format!("{id}.json")),
+                )?;
+                return Ok(Some(msg));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Settle `id` unread: its tell expired in the spool. The ack file
+    /// becomes an `.expired` marker, so a waiting sender can tell the two
+    /// apart — an expiry is not a read receipt.
+    pub fn expire(&self, id: &str, to: &str) -> Result<()> {
+        valid_name(id)?;
+        valid_name(to)?;
+        let dir = self.root.join("acks").join(to);
+        let owed = dir.join(format!("{id}.json"));
+        if owed.exists() {
+            fs::rename(owed, dir.join(format!("{id}.expired")))?;
+        }
+        Ok(())
+    }
+
+    pub fn is_acked(&self, to: &str, id: &str) -> bool {
+        let dir = self.root.join("acks").join(to);
+        !dir.join(format!("{id}.json")).exists() && !self.is_expired(to, id)
+    }
+
+    pub fn is_expired(&self, to: &str, id: &str) -> bool {
+        self.root
+            .join("acks")
+            .join(to)
+            .join(format!("{id}.expired"))
+            .exists()
+    }
+
+    /// Every tell still waiting on its ack, oldest first.
+    pub fn unacked_all(&self) -> Vec<Unacked> {
+        let Ok(agents) = fs::read_dir(self.root.join("acks")) else {
+            return Vec::new();
+        };
+        let mut out: Vec<Unacked> = agents
+            .filter_map(|e| e.ok())
+            .filter_map(|e| fs::read_dir(e.path()).ok())
+            .flatten()
+            .filter_map(|f| f.ok())
+            .filter(|f| f.path().extension().is_some_and(|x| x == "json"))
+            .filter_map(|f| fs::read(f.path()).ok())
+            .filter_map(|b| serde_json::from_slice::<Unacked>(&b).ok())
+            .collect();
+        out.sort_by_key(|m| m.ts);
+        out
+    }
+
+    /// When `name` last had something queued for someone else, unix
+    /// millis — a tell it sent that is still waiting is still activity.
+    pub fn spool_last_from(&self, name: &str) -> Option<u64> {
+        fs::read_dir(self.root.join("spool"))
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| fs::read_dir(e.path()).ok())
+            .flatten()
+            .filter_map(|f| f.ok())
+            .filter_map(|f| fs::read(f.path()).ok())
+            .filter_map(|b| serde_json::from_slice::<Spooled>(&b).ok())
+            .filter(|m| m.from == name)
+            .map(|m| m.ts)
+            .max()
+    }
+
     // ---- briefs ----
 
     /// Briefs are durable: a dialog (login picker, trust prompt) can eat
     /// the first delivery even when the pane looks interactive-ready, so
     /// the text is kept for re-delivery with `murmur tell <name> --brief`.
-    pub fn brief_save(&self, name: &str, text: &str) -> Result<()> {
+    pub fn brief_save(&self, name: &str, text: &str) -> Result<PathBuf> {
         valid_name(name)?;
         self.init()?;
-        fs::write(self.root.join("briefs").join(format!("{name}.txt")), text)?;
-        Ok(())
+        let path = self.root.join("briefs").join(format!("{name}.txt"));
+        fs::write(&path, text)?;
+        Ok(path)
     }
 
     pub fn brief_load(&self, name: &str) -> Result<String> {
@@ -200,9 +767,16 @@ impl Store {
 
     // ---- housekeeping ----
 
-    /// Drop spool files and briefs older than `age_secs`. Returns
+    /// Drop spool files, briefs, owed acks, and open asks older than
+    /// `age_secs`, and spooled tells past their own ttl. Returns
     /// (spooled_removed, briefs_removed).
     pub fn clean(&self, age_secs: u64) -> Result<(usize, usize)> {
+        let now = now_millis();
+        let read = |path: &Path| {
+            fs::read(path)
+                .ok()
+                .and_then(|b| serde_json::from_slice::<Spooled>(&b).ok())
+        };
         let mut spooled = 0;
         let spool = self.root.join("spool");
         if spool.is_dir() {
@@ -211,13 +785,44 @@ impl Store {
                     continue;
                 }
                 for f in fs::read_dir(agent_dir.path())?.filter_map(|e| e.ok()) {
-                    if file_older_than(&f.path(), age_secs) {
+                    let msg = read(&f.path());
+                    if file_older_than(&f.path(), age_secs)
+                        || msg.as_ref().is_some_and(|m| m.expired(now))
+                    {
                         let _ = fs::remove_file(f.path());
                         spooled += 1;
+                        // a tell nobody will ever see can't be acked
+                        if let Some(m) = msg.filter(|m| !m.id.is_empty()) {
+                            let _ = self.expire(&m.id, &m.to);
+                        }
+                    }
+                }
+            }
+        }
+        let acks = self.root.join("acks");
+        if acks.is_dir() {
+            for agent_dir in fs::read_dir(&acks)?.filter_map(|e| e.ok()) {
+                for f in fs::read_dir(agent_dir.path())?.filter_map(|e| e.ok()) {
+                    if file_older_than(&f.path(), age_secs) {
+                        let _ = fs::remove_file(f.path());
                     }
                 }
             }
         }
+        if let Ok(asks) = fs::read_dir(self.root.join("asks")) {
+            for f in asks.filter_map(|e| e.ok()) {
+                if file_older_than(&f.path(), age_secs) {
+                    let _ = fs::remove_file(f.path());
+                }
+            }
+        }
+        if let Ok(gates) = fs::read_dir(self.root.join("gates")) {
+            for f in gates.filter_map(|e| e.ok()) {
+                if file_older_than(&f.path(), age_secs) {
+                    let _ = fs::remove_file(f.path());
+                }
+            }
+        }
         let mut briefs = 0;
         let dir = self.root.join("briefs");
         if dir.is_dir() {
@@ -231,33 +836,150 @@ impl Store {
         Ok((spooled, briefs))
     }
 
-    // ---- herd snapshot (start/stop) ----
+    // ---- herd snapshots (start/stop) ----
+    //
+    // One file per wave, so a second `murmur start` never forgets the
+    // first and two starts never race on one file. A 0.7 notebook's single
+    // `herd.json` still reads as a wave until it is stopped.
+
+    fn herds_dir(&self) -> PathBuf {
+        self.root.join("herds")
+    }
 
-    fn herd_path(&self) -> PathBuf {
+    fn legacy_herd_path(&self) -> PathBuf {
         self.root.join("herd.json")
     }
 
     pub fn herd_save(&self, snap: &HerdSnap) -> Result<()> {
         self.init()?;
-        let tmp = self.root.join("tmp").join(format!("herd-{}", now_millis()));
+        let key = snap.key();
+        valid_name(&key)?;
+        fs::create_dir_all(self.herds_dir())?;
+        let tmp = self
+            .root
+            .join("tmp")
+            .join(format!("herd-{key}-{}", next_id(now_millis())));
         fs::write(&tmp, serde_json::to_vec(snap)?)?;
-        fs::rename(&tmp, self.herd_path())?;
+        fs::rename(&tmp, self.herds_dir().join(format!("{key}.json")))?;
         Ok(())
     }
 
+    /// Every wave in the notebook, oldest start first.
+    pub fn herd_list(&self) -> Result<Vec<HerdSnap>> {
+        let mut out: Vec<HerdSnap> = Vec::new();
+        if let Ok(entries) = fs::read_dir(self.herds_dir()) {
+            for e in entries.filter_map(|e| e.ok()) {
+                let path = e.path();
+                if path.extension().is_none_or(|x| x != "json") {
+                    continue;
+                }
+                if let Some(snap) = read_snap(&path) {
+                    out.push(snap);
+                }
+            }
+        }
+        if let Some(legacy) = read_snap(&self.legacy_herd_path()) {
+            if !out.iter().any(|s| s.key() == legacy.key()) {
+                out.push(legacy);
+            }
+        }
+        out.sort_by(|a, b| a.started.cmp(&b.started).then(a.key().cmp(&b.key())));
+        Ok(out)
+    }
+
+    /// The wave `sel` names, or — with no selector — the only or most
+    /// recent one. None when the notebook has no wave at all; an unknown
+    /// selector is an error naming the waves that do exist.
+    pub fn herd_select(&self, sel: Option<&str>) -> Result<Option<HerdSnap>> {
+        let mut waves = self.herd_list()?;
+        let Some(sel) = sel else {
+            return Ok(waves.pop());
+        };
+        if let Some(i) = waves.iter().rposition(|w| w.matches(sel)) {
+            return Ok(Some(waves.swap_remove(i)));
+        }
+        if waves.is_empty() {
+            fail!(
+                NotFound,
+                "no wave '{sel}' — this notebook has no running wave"
+            );
+        }
+        fail!(
+            NotFound,
+            "no wave '{sel}' — running: {}",
+            waves.iter().map(|w| w.key()).collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    /// The default wave: the only or most recent one.
     pub fn herd_load(&self) -> Result<Option<HerdSnap>> {
-        let path = self.herd_path();
-        if !path.exists() {
-            return Ok(None);
+        self.herd_select(None)
+    }
+
+    /// The newest wave that lists `agent` — names are unique across live
+    /// waves (start picks around herdr's live names), so this is *their*
+    /// wave.
+    pub fn herd_of(&self, agent: &str) -> Result<Option<HerdSnap>> {
+        Ok(self
+            .herd_list()?
+            .into_iter()
+            .rev()
+            .find(|w| w.agents.iter().any(|a| a == agent)))
+    }
+
+    /// Forget one wave (by key); the others stay.
+    pub fn herd_remove(&self, key: &str) -> Result<()> {
+        valid_name(key)?;
+        let _ = fs::remove_file(self.herds_dir().join(format!("{key}.json")));
+        if read_snap(&self.legacy_herd_path()).is_some_and(|s| s.key() == key) {
+            let _ = fs::remove_file(self.legacy_herd_path());
         }
-        let bytes = fs::read(&path)?;
-        Ok(serde_json::from_slice(&bytes).ok())
+        Ok(())
+    }
+
+    // ---- journal ----
+    //
+    // Append-only, never pruned by `clean` (only `clean --all`). Each
+    // event is one O_APPEND write of a whole line, so concurrent writers
+    // never interleave and a reader never sees a torn line.
+
+    fn journal_path(&self) -> PathBuf {
+        self.root.join("journal.jsonl")
     }
 
-    pub fn herd_clear(&self) -> Result<()> {
-        let _ = fs::remove_file(self.herd_path());
+    /// One line, appended in one write: concurrent agents never lose each
+    /// other's events.
+    pub fn journal_append(&self, event: &Event) -> Result<()> {
+        use std::io::Write;
+        self.init()?;
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(self.journal_path())?
+            .write_all(&line)?;
         Ok(())
     }
+
+    /// Every journal event, oldest first; unreadable lines are skipped.
+    pub fn journal_read(&self) -> Vec<Event> {
+        let Ok(text) = fs::read_to_string(self.journal_path()) else {
+            return Vec::new();
+        };
+        text.lines()
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect()
+    }
+}
+
+fn read_snap(path: &Path) -> Option<HerdSnap> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn gate_log_prefix(branch: &str) -> String {
+    branch.replace('/', "-")
 }
 
 fn file_older_than(path: &Path, age_secs: u64) -> bool {
@@ -309,6 +1031,21 @@ fn main_repo_root(start: &Path) -> Option<PathBuf> {
     }
 }
 
+/// The nearest `file` walking up from cwd — how FLEET.md and murmur.toml
+/// are found.
+pub fn find_up(file: &str) -> Option<PathBuf> {
+    let mut dir = std::env::current_dir().ok()?;
+    loop {
+        let candidate = dir.join(file);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
 /// Is `bin` an executable file on PATH? Adapters probe before shelling out.
 pub fn on_path(bin: &str) -> bool {
     let Some(paths) = std::env::var_os("PATH") else {
@@ -328,6 +1065,46 @@ pub fn now_secs() -> u64 {
     now_millis() / 1000
 }
 
+/// `90s`, `15m`, `2h`, `1d`, or bare seconds → seconds.
+pub fn parse_duration(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let (num, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let n: u64 = num
+        .parse()
+        .with_context(|| format!("bad duration '{s}' (try 90s, 15m, 2h, 1d)"))?;
+    let mult = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86_400,
+        _ => bail!("bad duration '{s}' (try 90s, 15m, 2h, 1d)"),
+    };
+    n.checked_mul(mult)
+        .with_context(|| format!("duration '{s}' is too long"))
+}
+
+/// A short message id an agent can type back: `m` + base-36 millis + a
+/// per-process sequence digit pair.
+pub fn message_id() -> String {
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let seq = SEQ.fetch_add(1, Ordering::Relaxed);
+    let mut n = now_millis();
+    let mut digits = Vec::new();
+    while n > 0 {
+        digits.push(b"0123456789abcdefghijklmnopqrstuvwxyz"[(n % 36) as usize]);
+        n /= 36;
+    }
+    digits.reverse();
+    format!(
+        "m{}{:02}",
+        String::from_utf8(digits).unwrap_or_default(),
+        (u64::from(std::process::id()) + seq) % 100
+    )
+}
+
 pub fn next_id(ts: u64) -> String {
     static SEQ: AtomicU64 = AtomicU64::new(0);
     let seq = SEQ.fetch_add(1, Ordering::Relaxed);
@@ -0,0 +1,183 @@
+//! `murmur sync` — a worker catches up with the integration branch.
+//!
+//! Restack never rewrites history, so a worker that falls behind the
+//! lead's branch meets the drift at merge time, in the lead's checkout.
+//! Sync moves that meeting into the worker's own worktree, early: it merges
+//! `herd/<slug>/<lead>` into the worker's `herd/<slug>/<name>`, reports
+//! what conflicted, and tells the lead when the conflict is on a hub path
+//! — the shared files the lead arbitrates. Same snapshot, same merges as
+//! restack, the other direction.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::process::Command;
+
+use crate::commands::{record, tell_or_spool};
+use crate::exit::{self, Kind};
+use crate::fail;
+use crate::restack::{git_out, hubs_touched};
+use crate::say;
+use crate::store::{Event, HerdSnap, Outcome, Store, Verb};
+
+/// `murmur sync --json`: what came in, or what conflicted.
+#[derive(Serialize)]
+pub struct SyncReport {
+    pub branch: String,
+    pub into: String,
+    /// up-to-date, merged, or conflict.
+    pub outcome: &'static str,
+    /// Commits merged in (or that would have been).
+    pub commits: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+    /// Conflicted hub paths; the lead was told about them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hubs: Vec<String>,
+}
+
+/// `murmur sync` — run from a worker's worktree.
+pub fn run(wave: Option<String>, from: Option<String>, json: bool) -> Result<()> {
+    crate::report::set_json(json);
+    let store = Store::locate()?;
+    let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let current = git_out(&cwd, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .context("sync runs inside your worktree")?;
+    let snap = select(&store, wave.as_deref(), &current, from)?;
+    let name = current
+        .strip_prefix(&format!("herd/{}/", snap.slug))
+        .with_context(|| {
+            format!(
+                "{current} is not a herd branch of {} — sync runs in a worker's worktree",
+                snap.key()
+            )
+        })?
+        .to_string();
+    let lead = snap
+        .agents
+        .first()
+        .context("the herd snapshot has no lead")?;
+    if name == *lead {
+        fail!(
+            Refused,
+            "{current} is the integration branch — `murmur restack` merges into it; sync is for workers"
+        );
+    }
+    let into = format!("herd/{}/{lead}", snap.slug);
+    git_out(&cwd, &["rev-parse", "--verify", "--quiet", &into])
+        .map_err(|_| exit::err(Kind::NotFound, format!("no branch {into} to sync with")))?;
+    let journal = |outcome: Outcome, note: &str| {
+        let mut event = Event::new(Verb::Sync, &name, &current, outcome);
+        event.wave = snap.key();
+        event.note = note.to_string();
+        record(&store, event);
+    };
+
+    let behind = git_out(&cwd, &["rev-list", "--count", &format!("HEAD..{into}")])?;
+    let mut report = SyncReport {
+        branch: current.clone(),
+        into: into.clone(),
+        outcome: "up-to-date",
+        commits: behind.parse().unwrap_or(0),
+        conflicts: Vec::new(),
+        hubs: Vec::new(),
+    };
+    let emit = |report: &SyncReport| match json {
+        true => crate::report::emit(report),
+        false => Ok(()),
+    };
+    if behind == "0" {
+        say!("{current} is up to date with {into}");
+        return emit(&report);
+    }
+    let dirty = git_out(&cwd, &["status", "--porcelain", "--untracked-files=no"])?;
+    if !dirty.is_empty() {
+        fail!(
+            Refused,
+            "uncommitted changes in your worktree — commit (or stash) them, then sync"
+        );
+    }
+    let merged = Command::new("git")
+        .args([
+            "merge",
+            "--no-edit",
+            "-m",
+            &format!("sync: merge {into}"),
+            &into,
+        ])
+        .current_dir(&cwd)
+        .output()
+        .context("failed to run git")?;
+    if merged.status.success() {
+        say!("synced {current} with {into} ({behind} commit(s))");
+        journal(Outcome::Merged, &format!("{behind} commit(s) from {into}"));
+        report.outcome = "merged";
+        return emit(&report);
+    }
+
+    let conflicts = git_out(&cwd, &["diff", "--name-only", "--diff-filter=U"])?;
+    if conflicts.is_empty() {
+        bail!(
+            "git merge {into} failed: {}",
+            String::from_utf8_lossy(&merged.stderr).trim()
+        );
+    }
+    let files: Vec<&str> = conflicts.lines().collect();
+    journal(Outcome::Conflict, &files.join(", "));
+    let hubs = hubs_touched(&snap.hubs, files.iter().copied());
+    if !hubs.is_empty() {
+        let body = format!(
+            "[sync] {name} merging {into} into {current} conflicts on hub path(s) {} \
+             (all conflicts: {}). Hubs are yours to arbitrate — tell {name} how to resolve.",
+            hubs.join(", "),
+            files.join(", ")
+        );
+        match tell_or_spool(&store, &name, lead, &body) {
+            Ok(_) => say!("told {lead}: the conflict touches {}", hubs.join(", ")),
+            Err(e) => eprintln!("warning: could not tell {lead}: {e:#}"),
+        }
+    }
+    report.outcome = "conflict";
+    report.conflicts = files.iter().map(|f| f.to_string()).collect();
+    report.hubs = hubs.iter().map(|h| h.to_string()).collect();
+    emit(&report)?;
+    fail!(
+        Conflict,
+        "sync conflicts in {}\nthe merge is in progress in your worktree: resolve, `git add`, \
+         `git commit` — or `git merge --abort` to back out",
+        files.join(", ")
+    )
+}
+
+/// The wave: named, else the one whose slug the branch carries, else the
+/// one the caller belongs to.
+fn select(
+    store: &Store,
+    wave: Option<&str>,
+    branch: &str,
+    from: Option<String>,
+) -> Result<HerdSnap> {
+    if wave.is_some() {
+        return store
+            .herd_select(wave)?
+            .ok_or_else(|| exit::err(Kind::NotFound, "no such wave — see `murmur status`"));
+    }
+    if let Some(slug) = branch
+        .strip_prefix("herd/")
+        .and_then(|rest| rest.split('/').next())
+    {
+        if let Ok(Some(snap)) = store.herd_select(Some(slug)) {
+            return Ok(snap);
+        }
+    }
+    match crate::commands::ambient(from) {
+        Some(me) => store.herd_of(&me)?,
+        None => store.herd_select(None)?,
+    }
+    .filter(|w| !w.slug.is_empty())
+    .ok_or_else(|| {
+        exit::err(
+            Kind::NotFound,
+            "no worktree herd here — sync serves a `murmur start --worktree` herd",
+        )
+    })
+}
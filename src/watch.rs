@@ -0,0 +1,220 @@
+//! `murmur status --watch` — the wave on one screen, kept current.
+//!
+//! The same sources `murmur status` reads (the herd snapshot, herdr's live
+//! view, the spool, beads) plus `murmur diff`'s git view and the git host's
+//! PR state, folded into one row per agent and redrawn every interval.
+//! A row that changed since the last frame is drawn in reverse video, so
+//! the eye goes where something happened. PR lookups hit the network, so
+//! they refresh on their own slower clock. Ctrl-C quits.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::commands::fmt_utc;
+use crate::githost::Host;
+use crate::store::{self, HerdSnap, Store};
+
+/// PR state is re-fetched at most this often.
+const PR_EVERY: Duration = Duration::from_secs(60);
+
+/// Branch → (when we asked, what the host said).
+type PrCache = HashMap<String, (Instant, String)>;
+
+pub fn run(wave: Option<String>, every_secs: u64) -> Result<()> {
+    let store = Store::locate()?;
+    let cwd = std::env::current_dir().context("cannot determine cwd")?;
+    let mut prs = PrCache::new();
+    let mut last: HashMap<String, String> = HashMap::new();
+    loop {
+        let (head, rows) = frame(&store, wave.as_deref(), &cwd, &mut prs)?;
+        let mut screen = String::from("\x1b[H\x1b[2J");
+        screen.push_str(&head);
+        screen.push('\n');
+        screen.push_str(&draw(&rows, &last));
+        let mut out = std::io::stdout().lock();
+        out.write_all(screen.as_bytes())?;
+        out.flush()?;
+        last = rows.into_iter().collect();
+        std::thread::sleep(Duration::from_secs(every_secs));
+    }
+}
+
+/// The rows, each one that differs from `last` in reverse video. The
+/// first frame (nothing before it) highlights nothing.
+fn draw(rows: &[(String, String)], last: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    for (key, line) in rows {
+        if !last.is_empty() && last.get(key) != Some(line) {
+            out.push_str(&format!("\x1b[7m{line}\x1b[0m\n"));
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// The header, then (key, line) rows — keys let the next frame tell what
+/// changed. The status view is `murmur status`'s own; the bead each agent
+/// holds, its drift, and its PR are the watch's additions.
+fn frame(
+    store: &Store,
+    wave: Option<&str>,
+    cwd: &Path,
+    prs: &mut PrCache,
+) -> Result<(String, Vec<(String, String)>)> {
+    let view = crate::commands::status_view(store, wave, cwd)?;
+    let snap = view.wave.as_ref();
+    let live = view.agents.unwrap_or_default();
+    let names: Vec<String> = match snap {
+        Some(w) => w.agents.clone(),
+        None => live.iter().map(|a| a.name.clone()).collect(),
+    };
+    let head = format!(
+        "murmur · wave {} · {} · ctrl-c quits",
+        snap.map(|w| w.key())
+            .filter(|k| !k.is_empty())
+            .unwrap_or_else(|| "none".into()),
+        fmt_utc(store::now_secs())
+    );
+
+    let held: HashMap<String, String> = if view.ready.is_some() {
+        crate::beads::in_progress_in(Some(cwd))
+            .unwrap_or_default()
+            .into_iter()
+            .rev() // the first bead an agent holds wins
+            .map(|i| (i.assignee, i.id))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let progress: HashMap<String, crate::progress::Progress> = snap
+        .map(crate::progress::collect)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| (p.agent.clone(), p))
+        .collect();
+    let host = snap
+        .map(|w| PathBuf::from(&w.repo))
+        .filter(|r| r.is_dir())
+        .and_then(|r| {
+            Host::detect(&r)
+                .ok()
+                .filter(|h| h.available())
+                .map(|h| (h, r))
+        });
+
+    let mut rows = vec![(
+        "columns".to_string(),
+        format!(
+            "{:<12} {:<9} {:<14} {:>5}  {:<12} PR",
+            "AGENT", "PANE", "BEAD", "SPOOL", "AHEAD/BEHIND"
+        ),
+    )];
+    for name in &names {
+        let pane = live
+            .iter()
+            .find(|a| a.name == *name)
+            .map(|a| a.status.clone())
+            .unwrap_or_else(|| "gone".into());
+        let drift = progress
+            .get(name)
+            .and_then(|p| Some(format!("{}/{}", p.ahead?, p.behind?)))
+            .unwrap_or_else(|| "—".into());
+        let pr = match (&host, snap) {
+            (Some((host, repo)), Some(w)) if !w.slug.is_empty() => {
+                pr_state(prs, host, repo, &format!("herd/{}/{name}", w.slug))
+            }
+            _ => "—".into(),
+        };
+        rows.push((
+            name.clone(),
+            format!(
+                "{:<12} {:<9} {:<14} {:>5}  {:<12} {}",
+                name,
+                pane,
+                held.get(name).map(String::as_str).unwrap_or("—"),
+                view.spool.get(name).copied().unwrap_or(0),
+                drift,
+                pr
+            ),
+        ));
+    }
+    if let Some(ready) = &view.ready {
+        let ready = ready
+            .as_ref()
+            .map(|r| crate::beads::leaves(r).len().to_string())
+            .unwrap_or_else(|_| "?".into());
+        rows.push((
+            "ready".into(),
+            format!("ready  {ready} unblocked leaf bead(s)"),
+        ));
+    }
+    if let Some(w) = snap {
+        rows.extend(others(w, &view.spool));
+    }
+    Ok((head, rows))
+}
+
+/// Spooled tells for names outside the herd (a human, a cloud agent).
+fn others(snap: &HerdSnap, spool: &BTreeMap<String, usize>) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = spool
+        .iter()
+        .filter(|(name, _)| !snap.agents.contains(name))
+        .map(|(name, n)| {
+            (
+                format!("spool:{name}"),
+                format!("spool  {name}: {n} queued tell(s)"),
+            )
+        })
+        .collect();
+    out.sort();
+    out
+}
+
+fn pr_state(prs: &mut PrCache, host: &Host, repo: &Path, branch: &str) -> String {
+    if let Some((at, state)) = prs.get(branch) {
+        if at.elapsed() < PR_EVERY {
+            return state.clone();
+        }
+    }
+    let state = match host.pr(repo, branch) {
+        Ok(Some(pr)) => pr.note(),
+        Ok(None) => "no PR".into(),
+        Err(_) => "?".into(),
+    };
+    prs.insert(branch.to_string(), (Instant::now(), state.clone()));
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(lines: &[(&str, &str)]) -> Vec<(String, String)> {
+        lines
+            .iter()
+            .map(|(k, l)| (k.to_string(), l.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn only_rows_that_changed_are_highlighted() {
+        let before = rows(&[("w1", "w1 idle"), ("w2", "w2 working")]);
+        assert_eq!(
+            draw(&before, &HashMap::new()),
+            "w1 idle\nw2 working\n",
+            "the first frame has nothing to compare with"
+        );
+        let last: HashMap<String, String> = before.into_iter().collect();
+        let now = rows(&[("w1", "w1 idle"), ("w2", "w2 idle"), ("w3", "w3 working")]);
+        assert_eq!(
+            draw(&now, &last),
+            "w1 idle\n\x1b[7mw2 idle\x1b[0m\n\x1b[7mw3 working\x1b[0m\n",
+            "a changed row and a new one stand out"
+        );
+    }
+}